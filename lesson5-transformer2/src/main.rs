@@ -1,7 +1,21 @@
-mod transformers;
 use std::io;
 use std::env;
-use transformers::transform;
+use transformers::{transform, Args};
+
+/// The command of the `transformers` library each parsing mode runs.
+fn command(mode: &str) -> Option<&'static str> {
+    match mode {
+        "lowercase" => Some("lower"),
+        "uppercase" => Some("upper"),
+        "no-space" => Some("nospace"),
+        "slugify" => Some("slugify"),
+        "title" => Some("title"),
+        "pascal" => Some("pascal"),
+        "password" => Some("passwordify"),
+        "csv" => Some("csv"),
+        _ => None,
+    }
+}
 
 /// Application's entry point.
 fn main() {
@@ -18,13 +32,11 @@ fn main() {
 
     let mode: &str = &args[1];
 
-    match mode {
-        "lowercase" | "uppercase" | "no-space" | "slugify" | "title" | "pascal" | "password" | "csv" => eprintln!("Parsing mode: [{}]", mode),
-        _ => {
-            eprintln!("Unsupported parsing mode: [{}]", mode);
-            return
-        }
-    }
+    let Some(command) = command(mode) else {
+        eprintln!("Unsupported parsing mode: [{mode}]");
+        return
+    };
+    eprintln!("Parsing mode: [{mode}]");
 
 
     loop {
@@ -37,7 +49,7 @@ fn main() {
                 let mut line = String::new();
                 _ = io::stdin().read_line(&mut line);
                 let line = line.trim();
-                if line == "" {
+                if line.is_empty() {
                     // end of Csv input reached.
                     break;
                 }
@@ -45,7 +57,7 @@ fn main() {
                 input.push('\n');
             }
 
-            println!("{}", input)
+            println!("{input}")
 
         }
         else {
@@ -53,8 +65,8 @@ fn main() {
         }
         let input = input.trim();
 
-        match transform(input.to_string(), mode) {
-            Ok(output) => println!("Output:\n{output}"),
+        match transform(input, command, &Args::default()) {
+            Ok(output) => println!("Output:\n{}", output.text),
             Err(e) => eprintln!("An error occurred: {e}"),
         }
 
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use transformers::{read_file, Args, Registry};
+
+/// How the batch mode is run, printed by `threaded-transform --help`.
+const USAGE: &str = "\
+Usage: threaded-transform COMMAND [OPTIONS] [TEXT...] [--input FILE] [--output FILE]
+       threaded-transform --script FILE [--continue-on-error] [--echo]
+       threaded-transform                  (runs the interactive prompt)
+
+Runs a single COMMAND (with its OPTIONS, see `threaded-transform help COMMAND`) on the TEXT,
+on the content of FILE given by --input, or on the standard input if neither is given. The
+output is written to the FILE given by --output, or to the standard output; the summary of
+the command (if any) is written to the standard error. A `--` ends the options.
+
+A script FILE holds prompt commands (see `source` in `threaded-transform help`), it stops at
+the first failed command unless --continue-on-error is given; --echo prints each command.
+
+Exit status: 0 on success, 1 if the command failed, 2 for an invalid command line,
+3 if the input couldn't be read or the output written.";
+
+/// The ways a batch run can fail, each one with its own exit status.
+enum Failure {
+    /// The command line is invalid (unknown command or options).
+    Usage(String),
+    /// The input couldn't be read or the output written.
+    Io(String),
+    /// The command itself failed.
+    Transform(String),
+}
+
+impl Failure {
+    /// The exit status of the failure.
+    fn status(&self) -> i32 {
+        match self {
+            Failure::Transform(_) => 1,
+            Failure::Usage(_) => 2,
+            Failure::Io(_) => 3,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Usage(e) => write!(f, "{e}\nType `threaded-transform --help` for the usage."),
+            Failure::Io(e) | Failure::Transform(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A command line of the batch mode.
+struct Invocation {
+    command: String,
+    args: Args,
+    /// The literal input, the words after the options joined with spaces.
+    text: Option<String>,
+    input: Option<String>,
+    output: Option<String>,
+}
+
+/// Reads the value of the `--input` or `--output` option, given either in
+/// the same token (`--input=FILE`) or in the next one.
+fn file_option(name: &str, inline: Option<&str>, tokens: &mut impl Iterator<Item = String>) -> Result<String, Failure> {
+    match inline {
+        Some(value) if !value.is_empty() => Ok(value.to_string()),
+        Some(_) => Err(Failure::Usage(format!("The option `--{name}` needs a file path"))),
+        None => tokens.next().ok_or(Failure::Usage(format!("The option `--{name}` needs a file path"))),
+    }
+}
+
+/// Parses the command line (without the program name) into an invocation.
+fn parse(cli: Vec<String>) -> Result<Invocation, Failure> {
+    let mut tokens = cli.into_iter();
+    let command = tokens.next().ok_or(Failure::Usage("Missing the command".to_string()))?;
+    let mut invocation = Invocation { command, args: Args::default(), text: None, input: None, output: None };
+    let mut words = vec![];
+
+    while let Some(token) = tokens.next() {
+        if token == "--" {
+            words.extend(tokens.by_ref());
+            break;
+        }
+        let option = match token.strip_prefix("--") {
+            Some(option) => option,
+            None => {
+                words.push(token);
+                continue;
+            },
+        };
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (option, None),
+        };
+        match name {
+            "input" => invocation.input = Some(file_option(name, value, &mut tokens)?),
+            "output" => invocation.output = Some(file_option(name, value, &mut tokens)?),
+            _ => invocation.args.push(option),
+        }
+    }
+
+    if !words.is_empty() {
+        invocation.text = Some(words.join(" "));
+    }
+    Ok(invocation)
+}
+
+/// Runs the invocation, writing its output (and summary) out.
+fn execute(registry: &Registry, invocation: Invocation) -> Result<(), Failure> {
+    let name = invocation.command.as_str();
+    let transform = registry.get(name)
+        .filter(|_| name != "exit")
+        .ok_or(Failure::Usage(format!("Un-recoginzed transformation method: [{name}]")))?;
+    Registry::check_args(transform, &invocation.args).map_err(Failure::Usage)?;
+
+    let output = match (invocation.text, invocation.input) {
+        (Some(_), Some(_)) => return Err(Failure::Usage("Both a TEXT and an `--input` file are given, only one is expected".to_string())),
+        // the commands streaming their files read them by themselves.
+        (None, Some(path)) if transform.streams_file() => registry.run_file(name, &path, &invocation.args),
+        (text, path) => {
+            let input = match (text, path) {
+                (Some(text), _) => text,
+                (None, Some(path)) => read_file(&path, invocation.args.get("sheet")).map_err(|e| Failure::Io(e.to_string()))?,
+                (None, None) if transform.takes_input() => {
+                    let mut input = String::new();
+                    io::stdin().read_to_string(&mut input).map_err(|e| Failure::Io(format!("Unable to read the standard input: {e}")))?;
+                    input
+                },
+                (None, None) => String::new(),
+            };
+            registry.run(name, input, &invocation.args)
+        },
+    };
+    let output = output.map_err(|e| Failure::Transform(e.to_string()))?;
+    let mut text = output.text;
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+
+    match invocation.output {
+        Some(path) => fs::write(&path, text).map_err(|e| Failure::Io(format!("Unable to write the file `{path}`: {e}")))?,
+        None => io::stdout().write_all(text.as_bytes()).map_err(|e| Failure::Io(format!("Unable to write the output: {e}")))?,
+    }
+    if let Some(summary) = output.summary {
+        eprintln!("{summary}");
+    }
+    Ok(())
+}
+
+/// Runs a single command given on the command line (without the program
+/// name) and returns the exit status of the program.
+pub fn run(registry: &Registry, cli: Vec<String>) -> i32 {
+    if matches!(cli.first().map(|s| s.as_str()), Some("--help" | "-h")) {
+        _ = writeln!(io::stdout(), "{USAGE}");
+        return 0;
+    }
+
+    match parse(cli).and_then(|invocation| execute(registry, invocation)) {
+        Ok(()) => 0,
+        Err(failure) => {
+            eprintln!("{failure}");
+            failure.status()
+        },
+    }
+}
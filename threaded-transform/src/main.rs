@@ -1,27 +1,62 @@
-use std::io::Read;
-use std::sync::mpsc;
+use std::env;
+use std::error::Error;
+use std::process;
+use std::sync::{mpsc, Arc};
 use std::thread;
-use std::fs::File;
 use prompt::Prompt;
-use prompt::event::Command;
 use prompt::event::Event;
 use prompt::event::EventType;
-use transformers::transform;
+use prompt::event::Stage;
+use prompt::script::Script;
+use transformers::{read_file, Args, Output, Registry};
 
 // modules
-mod transformers;
+mod batch;
+mod plugins;
 mod prompt;
 
 /// Application's entry point.
 fn main() {
+    let cli: Vec<String> = env::args().skip(1).collect();
+    // a single command run from the command line only writes its output.
+    let batch = cli.first().is_some_and(|arg| arg != "--script");
+
+    // the commands known to the prompt, in-house commands are added here with
+    // `registry.register(Box::new(...))`, the plugins ones at startup.
+    let mut registry = Registry::with_builtins();
+    if let Some(dir) = plugins::directory() {
+        plugins::load(&mut registry, &dir, batch);
+    }
+    let registry = Arc::new(registry);
+
+    // a script given on the command line is run by the prompt instead of the
+    // commands typed by the user, a command is run alone without the prompt.
+    let script = match cli.first().map(|s| s.as_str()) {
+        Some("--script") => match Script::from_cli(&cli[1..]) {
+            Ok(script) => Some(script),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(2);
+            },
+        },
+        Some(_) => process::exit(batch::run(&registry, cli)),
+        None => None,
+    };
+
     // creating channel for communication
     let (my_sender, my_receiver) = mpsc::channel::<Event>();
     
     // I give you my sender, you give me your sender and so we can exchange messages :)
-    let (prompt, prompt_sender) = Prompt::new(my_sender);
+    let (prompt, prompt_sender) = Prompt::new(my_sender, Arc::clone(&registry));
     
     // let's start the fun ;)
-    prompt.start();
+    let script_handle = match script {
+        Some((file, script)) => Some(prompt.start_script(file, script)),
+        None => {
+            prompt.start();
+            None
+        },
+    };
 
     let handle = thread::spawn(move || { 
         loop {
@@ -29,48 +64,73 @@ fn main() {
             let mut event = my_receiver.recv().unwrap();
 
             // process the command
-            if event.command == Command::Exit {
+            if event.command == "exit" {
                 eprintln!("Termination requested by the user, terminating...");
                 eprintln!("Send termination signal to the prompt thread...");
                 
                 let term_event = Event {
                     e_type: EventType::CommandOutput,
-                    command: Command::Exit,
-                    payload: "Termination requested by the user".to_string()
+                    command: "exit".to_string(),
+                    args: Args::default(),
+                    payload: "Termination requested by the user".to_string(),
+                    summary: None,
+                    pipeline: vec![],
                 };
                 _ = prompt_sender.send(term_event);
                 eprintln!("Termiation signal sent!");
                 break;
             }
 
-            // check if input is needed from file
-            if event.payload.starts_with("file:") {
+            // check if input is needed from file, the commands streaming their
+            // files read them by themselves.
+            let mut streamed = None;
+            if event.payload.starts_with("file:") && registry.get(&event.command).is_some_and(|t| t.streams_file()) {
+                streamed = Some(event.payload["file:".len()..].trim().to_string());
+            } else if event.payload.starts_with("file:") {
                 let (_, mut file_path) = event.payload.split_once(':').unwrap();
                 file_path = file_path.trim();
-                let mut file = File::open(file_path).expect("Unable to open the file");
-                let mut content = String::new();
-                file.read_to_string(&mut content).expect("Unable to read the file");
                 
                 // resetting the payload with the file content
                 // todo: this is not optimal, I think we should provide something like stream here, but this is a future work anyway.
-                event.payload = content;
+                match read_file(file_path, event.args.get("sheet")) {
+                    Ok(content) => event.payload = content,
+                    Err(e) => {
+                        let e_event = Event {
+                            e_type: EventType::CommandError,
+                            command: event.command.clone(),
+                            args: Args::default(),
+                            payload: e.to_string(),
+                            summary: None,
+                            pipeline: vec![],
+                        };
+                        prompt_sender.send(e_event).unwrap();
+                        continue;
+                    }
+                }
             }
 
             // start transformation
-            let result = transform(event.payload, event.command);
-            
+            let command = event.command.clone();
+            let result = run_pipeline(&registry, event, streamed.as_deref());
     
             let mut o_event = Event {
                 e_type: EventType::CommandOutput,
-                command: event.command,
-                payload: String::new()
+                command,
+                args: Args::default(),
+                payload: String::new(),
+                summary: None,
+                pipeline: vec![],
             };
     
-            if result.is_ok() {
-                o_event.payload = result.unwrap();
-            } else {
-                o_event.e_type = EventType::CommandError;
-                o_event.payload = result.unwrap_err().to_string();
+            match result {
+                Ok(output) => {
+                    o_event.payload = output.text;
+                    o_event.summary = output.summary;
+                },
+                Err(e) => {
+                    o_event.e_type = EventType::CommandError;
+                    o_event.payload = e.to_string();
+                }
             }
     
             // send output back to the prompt
@@ -80,4 +140,39 @@ fn main() {
 
     handle.join().unwrap();
     eprintln!("Program terminated!");
+
+    if let Some(script_handle) = script_handle {
+        process::exit(script_handle.join().unwrap());
+    }
+}
+
+/// Runs the command of the `event` on its payload (or on the `streamed` file
+/// it reads by itself), then each stage of its pipeline on the output of the
+/// previous one. The error of a failing stage tells which one it is, and the
+/// summaries of the stages are gathered.
+fn run_pipeline(registry: &Registry, event: Event, streamed: Option<&str>) -> Result<Output, Box<dyn Error>> {
+    let mut stages = vec![Stage { command: event.command, args: event.args }];
+    stages.extend(event.pipeline);
+    let run_first = |input: String| match streamed {
+        Some(path) => registry.run_file(&stages[0].command, path, &stages[0].args),
+        None => registry.run(&stages[0].command, input, &stages[0].args),
+    };
+    if stages.len() == 1 {
+        return run_first(event.payload);
+    }
+
+    let mut text = event.payload;
+    let mut summaries = vec![];
+    for (i, stage) in stages.iter().enumerate() {
+        let name = &stage.command;
+        let output = if i == 0 { run_first(text) } else { registry.run(name, text, &stage.args) }
+            .map_err(|e| format!("Stage {} (`{name}`) failed: {e}", i + 1))?;
+        text = output.text;
+        if let Some(summary) = output.summary {
+            summaries.push(format!("[{name}] {summary}"));
+        }
+    }
+
+    let summary = if summaries.is_empty() { None } else { Some(summaries.join("\n")) };
+    Ok(Output { text, summary })
 }
@@ -0,0 +1,232 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use transformers::{ArgSpec, Args, Output, Registry, Transform};
+use self::wasm::WasmModule;
+
+mod executable;
+mod wasm;
+
+// Transform plugins are loaded at startup out of the plugins directory and
+// registered as commands along with the built-in ones. A plugin is either a
+// WebAssembly module (`.wasm`) or an executable file, both speak the same line
+// protocol where each line is a `key: value` directive:
+//
+// * The description of the plugin (its help), printed by `PLUGIN --describe`
+//   or returned by the `describe` export of a module:
+//       name: rot13                         (default: the file name)
+//       description: Rotates letters by 13 places.
+//       option: shift N The number of places to rotate by.
+//       flag: upper Upper cases the output.
+//       reads-lines                         (takes multi-line input, like csv)
+//       no-input                            (takes no input, like password)
+// * The request sent to the plugin for a single run, its options (without
+//   their leading `--`) then its input lines:
+//       arg: shift=3
+//       input: Some Text
+// * The response of the plugin, any `error:` line makes the run fail:
+//       output: Vrph Whaw
+//       summary: 8 letters rotated
+//       error: Invalid shift `x`
+
+/// The directory plugins are loaded from when `TRANSFORM_PLUGINS` isn't set,
+/// relative to the user configuration directory.
+const DEFAULT_DIRECTORY: &str = "threaded-transform/plugins";
+
+/// The help and options of a plugin, read out of its description.
+struct Description {
+    name: String,
+    description: String,
+    args: Vec<ArgSpec>,
+    reads_lines: bool,
+    takes_input: bool,
+}
+
+impl Description {
+    /// Parses the description lines of the plugin at `path`, its name
+    /// defaults to the file name (without extension).
+    fn parse(text: &str, path: &Path) -> Result<Description, Box<dyn Error>> {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let mut description = Description {
+            name: stem.to_string(),
+            description: format!("Plugin loaded from `{}`.", path.display()),
+            args: vec![],
+            reads_lines: false,
+            takes_input: true,
+        };
+
+        for line in text.lines().map(|line| line.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(':').map_or((line, ""), |(k, v)| (k.trim(), v.trim()));
+            match key {
+                "name" => description.name = value.to_string(),
+                "description" => description.description = value.to_string(),
+                "option" => {
+                    let mut parts = value.splitn(3, char::is_whitespace);
+                    match (parts.next(), parts.next(), parts.next()) {
+                        (Some(name), Some(value), help) if !name.is_empty() => {
+                            description.args.push(ArgSpec::value(name, value, help.unwrap_or_default().trim()));
+                        },
+                        _ => return Err(format!("Invalid option `{value}`, expected `option: NAME VALUE HELP`").into()),
+                    }
+                },
+                "flag" => match value.split_once(char::is_whitespace) {
+                    Some((name, help)) => description.args.push(ArgSpec::flag(name, help.trim())),
+                    None if !value.is_empty() => description.args.push(ArgSpec::flag(value, "")),
+                    None => return Err("Invalid flag, expected `flag: NAME HELP`".into()),
+                },
+                "reads-lines" => description.reads_lines = true,
+                "no-input" => description.takes_input = false,
+                _ => return Err(format!("Unknown description line `{line}`").into()),
+            }
+        }
+
+        if description.name.is_empty() || description.name.contains(char::is_whitespace) {
+            return Err(format!("Invalid command name `{}`", description.name).into());
+        }
+        Ok(description)
+    }
+}
+
+/// How a plugin is run.
+enum Runner {
+    Executable(PathBuf),
+    Wasm(WasmModule),
+}
+
+/// A command loaded out of the plugins directory.
+struct Plugin {
+    description: Description,
+    runner: Runner,
+}
+
+impl Transform for Plugin {
+    fn name(&self) -> &str {
+        &self.description.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description.description
+    }
+
+    fn args(&self) -> Vec<ArgSpec> {
+        self.description.args.clone()
+    }
+
+    fn takes_input(&self) -> bool {
+        self.description.takes_input
+    }
+
+    fn reads_lines(&self) -> bool {
+        self.description.reads_lines
+    }
+
+    fn apply(&self, input: String, args: &Args) -> Result<Output, Box<dyn Error>> {
+        let request = request(&input, args);
+        let response = match &self.runner {
+            Runner::Executable(path) => executable::call(path, &request)?,
+            Runner::Wasm(module) => module.call(&request)?,
+        };
+        parse_response(&response)
+    }
+}
+
+/// Writes the request of a single run of a plugin.
+fn request(input: &str, args: &Args) -> String {
+    let mut request = String::new();
+    for (name, value) in args.iter() {
+        match value {
+            Some(value) => request.push_str(&format!("arg: {name}={value}\n")),
+            None => request.push_str(&format!("arg: {name}\n")),
+        }
+    }
+    for line in input.lines() {
+        request.push_str(&format!("input: {line}\n"));
+    }
+    request
+}
+
+/// Reads the response of a plugin into the output of the command.
+fn parse_response(response: &str) -> Result<Output, Box<dyn Error>> {
+    let mut output = vec![];
+    let mut summary = vec![];
+    let mut errors = vec![];
+    for line in response.lines() {
+        // the value starts right after `key: `, its own leading spaces are kept.
+        let (key, value) = line.split_once(':').ok_or(format!("Invalid response line `{line}` of the plugin"))?;
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match key {
+            "output" => output.push(value),
+            "summary" => summary.push(value),
+            "error" => errors.push(value),
+            _ => return Err(format!("Invalid response line `{line}` of the plugin").into()),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors.join("\n").into());
+    }
+    let summary = if summary.is_empty() { None } else { Some(summary.join("\n")) };
+    Ok(Output { text: output.join("\n"), summary })
+}
+
+/// The plugins directory, given by the `TRANSFORM_PLUGINS` environment
+/// variable or `threaded-transform/plugins` in the user configuration
+/// directory (`$XDG_CONFIG_HOME`, or `~/.config`); `None` (no plugins) without
+/// any of them.
+pub fn directory() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("TRANSFORM_PLUGINS") {
+        return Some(PathBuf::from(dir));
+    }
+    let config = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config.join(DEFAULT_DIRECTORY))
+}
+
+/// Loads the plugin at `path`, `None` for files that aren't plugins.
+fn load_plugin(path: &Path) -> Result<Option<Plugin>, Box<dyn Error>> {
+    let (runner, description) = if path.extension().is_some_and(|ext| ext == "wasm") {
+        let module = WasmModule::load(path)?;
+        let description = module.describe()?;
+        (Runner::Wasm(module), description)
+    } else if executable::is_executable(path) {
+        let description = executable::describe(path)?;
+        (Runner::Executable(path.to_path_buf()), description)
+    } else {
+        return Ok(None);
+    };
+
+    let description = Description::parse(&description, path)?;
+    Ok(Some(Plugin { description, runner }))
+}
+
+/// Registers the plugins found in `dir` (in file name order), each one is
+/// reported unless `quiet`; a plugin that fails to load is always reported and
+/// skipped. A missing directory has no plugins.
+pub fn load(registry: &mut Registry, dir: &Path, quiet: bool) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_file()).collect();
+    paths.sort();
+
+    for path in paths {
+        let result = load_plugin(&path).and_then(|plugin| match plugin {
+            Some(plugin) => {
+                let name = plugin.description.name.clone();
+                registry.register(Box::new(plugin))?;
+                Ok(Some(name))
+            },
+            None => Ok(None),
+        });
+        match result {
+            Ok(Some(name)) if !quiet => eprintln!("Plugin `{name}` loaded from `{}`", path.display()),
+            Ok(_) => {},
+            Err(e) => eprintln!("Unable to load the plugin `{}`: {e}", path.display()),
+        }
+    }
+}
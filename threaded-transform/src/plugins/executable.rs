@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long a plugin may run before it's killed.
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Checks whether the file at `path` can be run.
+#[cfg(unix)]
+pub fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+/// Checks whether the file at `path` can be run.
+#[cfg(not(unix))]
+pub fn is_executable(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "exe")
+}
+
+/// Checks the exit status of a plugin run, a failure is reported with what
+/// the plugin wrote to its standard error.
+fn check_status(path: &Path, output: &Output) -> Result<(), Box<dyn Error>> {
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut message = format!("The plugin `{}` failed ({})", path.display(), output.status);
+    if !stderr.trim().is_empty() {
+        message.push_str(&format!(": {}", stderr.trim()));
+    }
+    Err(message.into())
+}
+
+/// Runs the plugin at `path` with `args`, the `input` as its standard input,
+/// and returns its output. A plugin still running after `PLUGIN_TIMEOUT` is
+/// killed.
+fn run(path: &Path, args: &[&str], input: &str) -> Result<Output, Box<dyn Error>> {
+    let mut child = Command::new(path)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Unable to run the plugin `{}`: {e}", path.display()))?;
+
+    // the pipes are handled by other threads, so that a plugin writing its
+    // response before reading the whole request doesn't block both sides.
+    let mut stdin = child.stdin.take().ok_or("Unable to write to the plugin")?;
+    let input = input.to_string();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + PLUGIN_TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            _ = child.kill();
+            _ = child.wait();
+            return Err(format!("The plugin `{}` didn't answer within {} seconds", path.display(), PLUGIN_TIMEOUT.as_secs()).into());
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    // a plugin may exit without reading its input, the broken pipe is then ignored.
+    _ = writer.join();
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+    check_status(path, &output)?;
+    Ok(output)
+}
+
+/// Reads everything out of a pipe of a plugin in another thread.
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = vec![];
+        if let Some(mut pipe) = pipe {
+            _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Reads the description of the plugin printed by `PLUGIN --describe`.
+pub fn describe(path: &Path) -> Result<String, Box<dyn Error>> {
+    let output = run(path, &["--describe"], "")?;
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Runs the plugin at `path` with the `request` as its standard input and
+/// returns its response (standard output).
+pub fn call(path: &Path, request: &str) -> Result<String, Box<dyn Error>> {
+    let output = run(path, &[], request)?;
+    Ok(String::from_utf8(output.stdout)?)
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use wasmi::{Config, Engine, Instance, Linker, Memory, Module, Store};
+
+// The string-in/string-out ABI of the WebAssembly plugins. A module imports
+// nothing and exports:
+//
+// * `memory`: its linear memory, the strings (UTF-8) are exchanged through it.
+// * `alloc(len: i32) -> i32`: reserves `len` bytes, the request is written there.
+// * `transform(ptr: i32, len: i32) -> i64`: handles the request at `ptr` and
+//   returns where its response is, as `ptr << 32 | len`.
+// * `describe() -> i64` (optional): returns where the description is, the
+//   same way as `transform`.
+
+/// The fuel (roughly, the number of instructions) a module may use for a
+/// single call, so that a module stuck in a loop doesn't hang the prompt.
+const FUEL: u64 = 1_000_000_000;
+
+/// A compiled WebAssembly plugin, it's instantiated anew for each call so that
+/// no state is kept between runs.
+pub struct WasmModule {
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmModule {
+    /// Compiles the module at `path`.
+    pub fn load(path: &Path) -> Result<WasmModule, Box<dyn Error>> {
+        let bytes = fs::read(path).map_err(|e| format!("Unable to read the file `{}`: {e}", path.display()))?;
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, bytes).map_err(|e| format!("Invalid WebAssembly module: {e}"))?;
+        Ok(WasmModule { engine, module })
+    }
+
+    /// Creates a fresh instance of the module along with its memory.
+    fn instantiate(&self) -> Result<(Store<()>, Instance, Memory), Box<dyn Error>> {
+        let mut store = Store::new(&self.engine, ());
+        store.set_fuel(FUEL)?;
+        let instance = Linker::<()>::new(&self.engine).instantiate_and_start(&mut store, &self.module)?;
+        let memory = instance.get_memory(&store, "memory").ok_or("The module doesn't export its `memory`")?;
+        Ok((store, instance, memory))
+    }
+
+    /// Reads the string a packed `ptr << 32 | len` result points at.
+    fn read_string(store: &Store<()>, memory: &Memory, packed: i64) -> Result<String, Box<dyn Error>> {
+        let ptr = (packed as u64 >> 32) as usize;
+        let len = (packed as u64 & 0xffff_ffff) as usize;
+        if ptr.checked_add(len).is_none_or(|end| end > memory.data_size(store)) {
+            return Err(format!("Invalid string returned by the module: {len} bytes at {ptr} are out of its memory").into());
+        }
+        let mut buffer = vec![0; len];
+        memory.read(store, ptr, &mut buffer).map_err(|e| format!("Invalid string returned by the module: {e}"))?;
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Reads the description returned by the `describe` export, an empty one
+    /// if the module doesn't export it.
+    pub fn describe(&self) -> Result<String, Box<dyn Error>> {
+        let (mut store, instance, memory) = self.instantiate()?;
+        let describe = match instance.get_typed_func::<(), i64>(&store, "describe") {
+            Ok(describe) => describe,
+            Err(_) => return Ok(String::new()),
+        };
+        let packed = describe.call(&mut store, ()).map_err(|e| format!("The module failed: {e}"))?;
+        Self::read_string(&store, &memory, packed)
+    }
+
+    /// Passes the `request` to the `transform` export and returns its response.
+    pub fn call(&self, request: &str) -> Result<String, Box<dyn Error>> {
+        let (mut store, instance, memory) = self.instantiate()?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")
+            .map_err(|e| format!("The module doesn't export `alloc(len: i32) -> i32`: {e}"))?;
+        let transform = instance.get_typed_func::<(i32, i32), i64>(&store, "transform")
+            .map_err(|e| format!("The module doesn't export `transform(ptr: i32, len: i32) -> i64`: {e}"))?;
+
+        let len = i32::try_from(request.len()).map_err(|_| "The input is too large for the module")?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, request.as_bytes())
+            .map_err(|e| format!("Unable to write the request to the module memory: {e}"))?;
+        let packed = transform.call(&mut store, (ptr, len)).map_err(|e| format!("The module failed: {e}"))?;
+        Self::read_string(&store, &memory, packed)
+    }
+}
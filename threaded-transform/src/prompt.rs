@@ -1,8 +1,15 @@
-use std::{io, sync::mpsc::{Sender, Receiver, self}, thread, str::FromStr};
-use crate::prompt::event::{Command, EventType};
-use self::event::{Event, CommandParseErr};
+use std::{fs, path::Path, sync::{mpsc::{Sender, Receiver, self}, Arc}, thread::{self, JoinHandle}};
+use crate::prompt::event::EventType;
+use transformers::Registry;
+use transformers::Args;
+use self::event::{Event, Stage};
+use self::editor::LineEditor;
+use self::script::{Directive, Script};
 
+mod completion;
+mod editor;
 pub mod event;
+pub mod script;
 
 // Encapsulates the view of the application, it is responsible for taking input 
 // from the user, parsing it into a valid command, then send it as an eveto to a
@@ -11,15 +18,17 @@ pub mod event;
 pub struct Prompt {
     sender: Sender<Event>,
     receiver: Receiver<Event>,
+    registry: Arc<Registry>,
 }
 
 impl Prompt {
-    pub fn new(sender: Sender<Event>) -> (Prompt, Sender<Event>) {
+    pub fn new(sender: Sender<Event>, registry: Arc<Registry>) -> (Prompt, Sender<Event>) {
         // creating the prompt channel
         let (my_sender, my_receiver) = mpsc::channel::<Event>();
         let prompt = Prompt {
-            sender: sender,
+            sender,
             receiver: my_receiver,
+            registry,
         };
 
         (prompt, my_sender)
@@ -27,46 +36,58 @@ impl Prompt {
 
     pub fn start(self) {
         thread::spawn(move || {
+            let mut editor = LineEditor::new(Arc::clone(&self.registry));
             loop {
                 println!("[Awaiting your command, Sir]:");
-                let mut input = String::new();
 
-                _ = io::stdin().read_line(&mut input);
+                // the end of the input (Ctrl-D) terminates the program like `exit`.
+                let input = editor.read_command("> ").unwrap_or("exit".to_string());
                 let input = input.trim();
 
-                if input == "" {
+                if input.is_empty() {
                     // no command entered, continue without doing anything.
                     continue;
                 }
 
-                let result = parse_input(&input);
-
-                if result.is_err() {
-                    eprintln!("Sir.. `{}` is not recognized as a command! Please try again.", input);
-                    eprintln!("For a list of available commands, just type `help` (without the single qoutes).");
-                    eprintln!();
-                    continue;
+                // a script is run with its own state, an `exit` in it ends the program.
+                if let Some(directive) = Directive::parse(input) {
+                    let result = match directive {
+                        Ok(Directive::Source(file)) => self.source(Path::new(&file), &mut Script::default()),
+                        Ok(_) => Err("Sir.. `set`, `on-error` and `echo` are only available in scripts.".to_string()),
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(true) => continue,
+                        Ok(false) => break,
+                        Err(e) => {
+                            eprintln!("{e}");
+                            eprintln!();
+                            continue;
+                        },
+                    }
                 }
 
-                let (command, payload) = result.unwrap();
-
-                let mut event = Event {
-                    e_type: EventType::CommandInput,
-                    command: command,
-                    payload: payload,
+                let mut event = match parse_input(input, &self.registry) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        eprintln!("{e}");
+                        eprintln!("For a list of available commands, just type `help` (without the single qoutes).");
+                        eprintln!();
+                        continue;
+                    }
                 };
 
                 // recieve command here
-                if event.command == Command::Csv {
+                if self.registry.get(&event.command).is_some_and(|t| t.reads_lines()) {
 
                     // append the first line
                     event.payload.push('\n');
 
-                    loop { // read all next lines
-                        let mut line = String::new();
-                        _ = io::stdin().read_line(&mut line);
-                        let line = line.trim();
-                        if line == "" {
+                    // read all next lines
+                    while let Some(line) = editor.read_line() {
+                        // the lines are kept as is, the padding of fixed-width columns matters.
+                        let line = line.trim_end_matches(['\r', '\n']);
+                        if line.trim().is_empty() {
                             // end of Csv input reached.
                             break;
                         }
@@ -75,37 +96,283 @@ impl Prompt {
                     }
                 }
 
-                // send event to the processor (the main)
-                self.sender.send(event).expect("Problem while sending to channel");
-        
-                // recieve the output from the processor (the main)
-                let output_event = self.receiver.recv().unwrap();
+                let output_event = self.execute(event);
 
                 // before printing the output, check if this is a term signal
-                if output_event.command == Command::Exit {
+                if output_event.command == "exit" {
                     break; // break to exit the thread without panic.
                 }
-
-                println!("");
-                println!("");
-                println!("{}", output_event.payload);
-                println!("");
+                print_output(&output_event);
             }
         });
     }
+
+    /// Runs the script `file` (see `--script`) instead of reading commands,
+    /// then terminates the processor. The thread returns the exit status of
+    /// the program: 1 if the script failed or any of its commands did.
+    pub fn start_script(self, file: String, mut script: Script) -> JoinHandle<i32> {
+        thread::spawn(move || {
+            let result = self.source(Path::new(&file), &mut script);
+            if let Err(e) = &result {
+                eprintln!("{e}");
+            }
+            if !matches!(result, Ok(false)) {
+                // the script didn't `exit` by itself, the processor is still waiting.
+                self.execute(parse_input("exit", &self.registry).expect("`exit` is a built-in command"));
+            }
+            if result.is_err() || script.failures > 0 { 1 } else { 0 }
+        })
+    }
+
+    /// Sends the `event` to the processor (the main) and returns the output
+    /// it sends back.
+    fn execute(&self, event: Event) -> Event {
+        // send event to the processor (the main)
+        self.sender.send(event).expect("Problem while sending to channel");
+
+        // recieve the output from the processor (the main)
+        self.receiver.recv().unwrap()
+    }
+
+    /// Runs the commands saved in the script at `path`, one per line, like
+    /// they'd be typed in the prompt: see `Directive` for the lines handled by
+    /// the script itself. Lines starting with `#` are comments, the lines right
+    /// after a multi-line command (like `csv`) are its input up to an empty
+    /// line. Returns `false` once the processor is terminated by an `exit`.
+    fn source(&self, path: &Path, script: &mut Script) -> Result<bool, String> {
+        let content = Self::open(path, script)?;
+        let result = self.run_lines(path, &content, script);
+        script.leave();
+        result
+    }
+
+    /// Reads the script at `path` and marks it as running (see `Script::enter`).
+    fn open(path: &Path, script: &mut Script) -> Result<String, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Unable to read the script `{}`: {e}", path.display()))?;
+        script.enter(path)?;
+        Ok(content)
+    }
+
+    /// Runs the `content` lines of the script at `path`, see `source`.
+    fn run_lines(&self, path: &Path, content: &str, script: &mut Script) -> Result<bool, String> {
+        let mut lines = content.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let location = format!("{}:{}", path.display(), i + 1);
+
+            let line = match script.expand(line) {
+                Ok(line) => line,
+                Err(e) => {
+                    script.fail(&location, &e)?;
+                    continue;
+                },
+            };
+            if script.echo {
+                println!("> {line}");
+            }
+
+            match Directive::parse(&line) {
+                Some(Ok(Directive::Source(file))) => {
+                    let file = script.resolve(&file);
+                    // the failures of the sourced script are recorded (at their own
+                    // location) while running it, only opening it fails on this line.
+                    match Self::open(&file, script) {
+                        Ok(content) => {
+                            let result = self.run_lines(&file, &content, script);
+                            script.leave();
+                            if !result? {
+                                return Ok(false);
+                            }
+                        },
+                        Err(e) => script.fail(&location, &e)?,
+                    }
+                    continue;
+                },
+                Some(Ok(directive)) => {
+                    script.apply(directive);
+                    continue;
+                },
+                Some(Err(e)) => {
+                    script.fail(&location, &e)?;
+                    continue;
+                },
+                None => {},
+            }
+
+            let mut event = match parse_input(&line, &self.registry) {
+                Ok(event) => event,
+                Err(e) => {
+                    script.fail(&location, &e)?;
+                    continue;
+                },
+            };
+            if self.registry.get(&event.command).is_some_and(|t| t.reads_lines()) {
+                event.payload.push('\n');
+                for (_, line) in lines.by_ref() {
+                    let line = line.trim_end_matches(['\r', '\n']);
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    event.payload.push_str(line);
+                    event.payload.push('\n');
+                }
+            }
+
+            let output_event = self.execute(event);
+            if output_event.command == "exit" {
+                return Ok(false);
+            }
+            if output_event.e_type == EventType::CommandError {
+                script.fail(&location, &output_event.payload)?;
+            } else {
+                print_output(&output_event);
+            }
+        }
+        Ok(true)
+    }
 }
 
-fn parse_input(input: &str) -> Result<(Command, String), CommandParseErr> {
-    let tokens: Vec<&str> = input.split(' ').collect();
-    let command = Command::from_str(tokens[0]);
-    if command.is_err() {
-        return Err(command.err().unwrap());
+/// Prints the output of a command (with its summary) to the user.
+fn print_output(output_event: &Event) {
+    println!();
+    println!();
+    println!("{}", output_event.payload);
+    if let Some(summary) = &output_event.summary {
+        println!();
+        println!("{summary}");
     }
+    println!();
+}
 
+/// Parses a line of input into a command event, the line may be a pipeline
+/// of commands separated by ` | ` (like `lower Some Text | nospace`) which
+/// can also start with its input alone (like `file:data.csv | csv`).
+fn parse_input(input: &str, registry: &Registry) -> Result<Event, String> {
+    let segments = split_pipeline(input);
+    let first_name = segments[0].split(' ').next().unwrap_or_default();
+    let mut stages = vec![];
     let mut payload = String::new();
-    if tokens.len() > 1 {
-        payload = tokens[1..].join(" ");
+
+    for (i, segment) in segments.iter().enumerate() {
+        if i == 0 && registry.get(first_name).is_none() && segments.len() > 1 {
+            payload = segment.to_string();
+            continue;
+        }
+        let (command, mut args, text) = parse_command(segment, registry)?;
+        if i == 0 {
+            payload = text;
+        } else if !text.is_empty() {
+            // a later stage takes its input from the previous one, a bare word
+            // may only set its main option (like `hash sha256`).
+            let option = registry.get(&command).and_then(|t| t.positional()).filter(|_| !text.contains(' '))
+                .ok_or(format!("Sir.. `{segment}` takes its input from the previous stage, it can't have one of its own."))?;
+            if args.get(option).is_some() {
+                return Err(format!("Sir.. `{segment}` gives its `--{option}` twice."));
+            }
+            args.push(&format!("{option}={text}"));
+        }
+        stages.push(Stage { command, args });
+    }
+    if stages.len() > 1 && stages.iter().any(|stage| stage.command == "exit") {
+        return Err("Sir.. `exit` can't be part of a pipeline.".to_string());
     }
 
-    Ok((command.unwrap(), payload))
-}
\ No newline at end of file
+    let first = stages.remove(0);
+    Ok(Event {
+        e_type: EventType::CommandInput,
+        command: first.command,
+        args: first.args,
+        payload,
+        summary: None,
+        pipeline: stages,
+    })
+}
+
+/// Splits the input into the segments of a pipeline on ` | `, a ` | ` inside
+/// double quotes (like in `--with="a | b"`) is kept as is.
+fn split_pipeline(input: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in input.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '|' if !quoted && input[..i].ends_with(' ') && input[i + 1..].starts_with(' ') => {
+                segments.push(input[start..i].trim());
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    segments.push(input[start..].trim());
+    segments
+}
+
+/// Parses a single command with its options (checked against the ones it
+/// accepts) and payload.
+fn parse_command(input: &str, registry: &Registry) -> Result<(String, Args, String), String> {
+    let (name, mut rest) = input.split_once(' ').unwrap_or((input, ""));
+    let transform = registry.get(name)
+        .ok_or(format!("Sir.. `{input}` is not recognized as a command! Please try again."))?;
+
+    // every `--option` token right after the command belongs to it, a bare
+    // `--` ends the options so the payload itself can start with dashes.
+    let mut args = Args::default();
+    while let Some((option, remaining)) = next_option(rest) {
+        rest = remaining;
+        if option.is_empty() {
+            break;
+        }
+        args.push(&option);
+    }
+    Registry::check_args(transform, &args)?;
+
+    Ok((name.to_string(), args, rest.to_string()))
+}
+
+/// Splits the next `--option` token off the start of `rest`, returns it
+/// without its leading dashes along with what follows it. A value quoted
+/// with double quotes (`--with="a b"`) may hold spaces.
+fn next_option(rest: &str) -> Option<(String, &str)> {
+    let token = rest.strip_prefix("--")?;
+    let space = token.find(' ').unwrap_or(token.len());
+    let (option, end) = match token.find("=\"") {
+        Some(quote) if quote < space => {
+            let value_start = quote + 2;
+            match token[value_start..].find('"') {
+                Some(length) => (format!("{}={}", &token[..quote], &token[value_start..value_start + length]), value_start + length + 1),
+                None => (token[..space].to_string(), space),
+            }
+        },
+        _ => (token[..space].to_string(), space),
+    };
+    let remaining = &token[end..];
+    Some((option, remaining.strip_prefix(' ').unwrap_or(remaining)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_pipeline_with_a_bare_option() {
+        let registry = Registry::with_builtins();
+        let event = parse_input("lower Some Text | nospace | hash sha256", &registry).unwrap();
+
+        assert_eq!(event.command, "lower");
+        assert_eq!(event.payload, "Some Text");
+        let stages: Vec<&str> = event.pipeline.iter().map(|stage| stage.command.as_str()).collect();
+        assert_eq!(stages, vec!["nospace", "hash"]);
+        assert_eq!(event.pipeline[1].args.get("algo"), Some("sha256"));
+    }
+
+    #[test]
+    fn rejects_the_input_of_a_later_stage() {
+        let registry = Registry::with_builtins();
+        assert!(parse_input("lower Some Text | upper More Text", &registry).is_err());
+        assert!(parse_input("lower Some Text | hash --algo=md5 sha256", &registry).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use transformers::Registry;
+
+/// The commands of the prompt itself, completed along with the registry ones.
+const PROMPT_COMMANDS: [&str; 1] = ["source"];
+
+/// Completes the word under the cursor on Tab: the command names at the start
+/// of each stage of a pipeline, the options of the command right after it, and
+/// the file paths after `file:` (or `source`). The command names and options
+/// come from the `Registry` the commands are parsed with.
+pub struct PromptCompleter {
+    registry: Arc<Registry>,
+}
+
+impl PromptCompleter {
+    pub fn new(registry: Arc<Registry>) -> PromptCompleter {
+        PromptCompleter { registry }
+    }
+
+    /// The command names starting with `prefix`.
+    fn commands(&self, prefix: &str) -> Vec<Pair> {
+        self.registry.names()
+            .chain(PROMPT_COMMANDS)
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| Pair { display: name.to_string(), replacement: format!("{name} ") })
+            .collect()
+    }
+
+    /// The options of the `command` starting with `prefix` (with its dashes),
+    /// the ones taking a value are completed up to their `=`.
+    fn options(&self, command: &str, prefix: &str) -> Vec<Pair> {
+        let transform = match self.registry.get(command) {
+            Some(transform) => transform,
+            None => return vec![],
+        };
+        let mut options: Vec<(String, bool)> = transform.args().into_iter().map(|arg| (arg.name, arg.value.is_some())).collect();
+        if transform.takes_input() {
+            options.push(("sheet".to_string(), true));
+        }
+
+        options.into_iter()
+            .map(|(name, takes_value)| (format!("--{name}"), takes_value))
+            .filter(|(option, _)| option.starts_with(prefix))
+            .map(|(option, takes_value)| Pair {
+                replacement: if takes_value { format!("{option}=") } else { format!("{option} ") },
+                display: option,
+            })
+            .collect()
+    }
+}
+
+/// The files and directories starting with `prefix` (a partial path), the
+/// directories end with a `/` so their content can be completed next. Hidden
+/// ones are left out unless the prefix asks for them.
+fn paths(prefix: &str) -> Vec<Pair> {
+    let (dir, name_prefix) = match prefix.rfind('/') {
+        Some(slash) => prefix.split_at(slash + 1),
+        None => ("", prefix),
+    };
+    let entries = match fs::read_dir(if dir.is_empty() { Path::new(".") } else { Path::new(dir) }) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    let mut pairs: Vec<Pair> = entries.flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if !name.starts_with(name_prefix) || (name.starts_with('.') && !name_prefix.starts_with('.')) {
+                return None;
+            }
+            let name = if entry.path().is_dir() { format!("{name}/") } else { name };
+            Some(Pair { replacement: format!("{dir}{name}"), display: name })
+        })
+        .collect();
+    pairs.sort_by(|a, b| a.display.cmp(&b.display));
+    pairs
+}
+
+impl Completer for PromptCompleter {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        // the stage of the pipeline the cursor is in, and the word under it.
+        let stage_start = before.rfind(" | ").map_or(0, |i| i + 3);
+        let stage_start = stage_start + (before[stage_start..].len() - before[stage_start..].trim_start().len());
+        let word_start = before.rfind(' ').map_or(0, |i| i + 1).max(stage_start);
+        let word = &before[word_start..];
+
+        // a pipeline may start with its input alone, like `file:data.csv | csv`.
+        if let Some(path) = word.strip_prefix("file:") {
+            return Ok((word_start + "file:".len(), paths(path)));
+        }
+        if word_start == stage_start {
+            return Ok((word_start, self.commands(word)));
+        }
+
+        let mut tokens = before[stage_start..word_start].split_whitespace();
+        let command = tokens.next().unwrap_or_default();
+        if command == "source" {
+            return Ok((word_start, paths(word)));
+        }
+        // the options come right after the command, before its input.
+        if word.starts_with('-') && tokens.all(|token| token.starts_with("--")) {
+            return Ok((word_start, self.options(command, word)));
+        }
+        Ok((pos, vec![]))
+    }
+}
+
+impl Hinter for PromptCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for PromptCompleter {}
+
+impl Validator for PromptCompleter {}
+
+impl Helper for PromptCompleter {}
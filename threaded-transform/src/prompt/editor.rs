@@ -0,0 +1,107 @@
+use std::env;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{CompletionType, Config, Editor};
+use transformers::Registry;
+use crate::prompt::completion::PromptCompleter;
+use crate::prompt::split_pipeline;
+
+/// The number of commands kept in the history.
+const HISTORY_SIZE: usize = 1000;
+
+/// The history file, given by the `TRANSFORM_HISTORY` environment variable or
+/// `.threaded-transform-history` in the home directory; `None` (no history
+/// kept across sessions) without either.
+fn history_file() -> Option<PathBuf> {
+    if let Some(file) = env::var_os("TRANSFORM_HISTORY") {
+        return Some(PathBuf::from(file));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".threaded-transform-history"))
+}
+
+/// Whether a command line takes a secret as its payload (a password to
+/// check, or a `hash` compared to a digest), those are never saved to the
+/// history.
+fn is_secret(line: &str) -> bool {
+    split_pipeline(line).into_iter().any(|stage| {
+        let mut words = stage.split(' ');
+        match words.next() {
+            Some("strength" | "passwordify") => true,
+            Some("hash") => words.any(|word| word.starts_with("--verify")),
+            _ => false,
+        }
+    })
+}
+
+/// Creates the history file readable by the user only, the commands may
+/// contain sensitive data.
+fn create_history(file: &Path) {
+    let mut options = OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    _ = options.open(file);
+}
+
+/// Reads the lines typed by the user with readline-style editing: cursor
+/// movement, word deletion (Ctrl-W, Alt-D...), up/down history recall,
+/// Ctrl-R incremental search and Tab completion (see `PromptCompleter`, the
+/// candidates are listed when there are several). The commands are saved to
+/// the history file as soon as they're entered, so the history is kept across
+/// sessions, except the ones starting with a space and the ones taking a
+/// secret (see `is_secret`). Input that isn't a terminal (like a pipe) is read
+/// line by line as is.
+pub struct LineEditor {
+    editor: Editor<PromptCompleter, DefaultHistory>,
+    history: Option<PathBuf>,
+}
+
+impl LineEditor {
+    pub fn new(registry: Arc<Registry>) -> LineEditor {
+        let config = Config::builder()
+            .max_history_size(HISTORY_SIZE)
+            .and_then(|builder| builder.history_ignore_dups(true))
+            .map(|builder| builder.history_ignore_space(true).auto_add_history(false).completion_type(CompletionType::List).build())
+            .unwrap_or_default();
+        let mut editor = Editor::with_config(config).expect("Unable to set up the line editor");
+        editor.set_helper(Some(PromptCompleter::new(registry)));
+
+        let history = history_file();
+        if let Some(file) = &history {
+            // there is no history yet on the first session.
+            _ = editor.load_history(file);
+        }
+        LineEditor { editor, history }
+    }
+
+    /// Reads a command and adds it to the history (unless it starts with a
+    /// space or takes a secret). An interrupted line (Ctrl-C) is read as an
+    /// empty one, `None` at the end of the input (Ctrl-D).
+    pub fn read_command(&mut self, prompt: &str) -> Option<String> {
+        let line = match self.editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => return Some(String::new()),
+            Err(_) => return None,
+        };
+        if !line.trim().is_empty() && !is_secret(line.trim_start()) && self.editor.add_history_entry(line.as_str()).unwrap_or(false) {
+            if let Some(file) = &self.history {
+                // a history that can't be saved shouldn't get in the way of the commands.
+                create_history(file);
+                _ = self.editor.append_history(file);
+            }
+        }
+        Some(line)
+    }
+
+    /// Reads a line of a multi-line input (which isn't added to the history),
+    /// `None` at the end of the input or when interrupted.
+    pub fn read_line(&mut self) -> Option<String> {
+        self.editor.readline("").ok()
+    }
+}
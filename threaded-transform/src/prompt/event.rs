@@ -1,52 +1,30 @@
-use std::str::FromStr;
+use transformers::Args;
 
 pub struct Event {
     pub e_type: EventType,
-    pub command: Command,
+    /// The name of the command, as registered in the `Registry`.
+    pub command: String,
+    pub args: Args,
     pub payload: String,
+    /// A short summary of what the command did, printed after its output.
+    pub summary: Option<String>,
+    /// The commands run one after the other on the output of `command`, for
+    /// a pipeline like `lower Some Text | nospace | hash`.
+    pub pipeline: Vec<Stage>,
 }
 
-#[derive(Debug)]
-pub struct CommandParseErr;
-
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Command {
-    Lower,
-    Upper,
-    Slugify,
-    Nospace,
-    Title,
-    Pascal,
-    Passwordify,
-    Csv,
-    Help,
-    Exit,
+/// A command of a pipeline (with its options), it takes the output of the
+/// previous command as its input.
+#[derive(Clone, Debug)]
+pub struct Stage {
+    pub command: String,
+    pub args: Args,
 }
 
 #[derive(PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum EventType {
     CommandInput,
     CommandOutput,
     CommandError,
 }
-
-impl FromStr for Command {
-    type Err = CommandParseErr;
-    
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "lower" => Ok(Command::Lower),
-            "upper" => Ok(Command::Upper),
-            "nospace" => Ok(Command::Nospace),
-            "slugify" => Ok(Command::Slugify),
-            "title" => Ok(Command::Title),
-            "pascal" => Ok(Command::Pascal),
-            "password" => Ok(Command::Passwordify),
-            "csv" => Ok(Command::Csv),
-            "help" => Ok(Command::Help),
-            "exit" => Ok(Command::Exit),
-            _ => Err(CommandParseErr),
-        }
-    }
-
-}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A line of a script handled by the script itself instead of being sent to
+/// the processor as a command.
+pub enum Directive {
+    /// `set NAME=VALUE`: defines a variable, used as `$NAME` or `${NAME}`.
+    Set(String, String),
+    /// `on-error stop|continue`: whether a failed command stops the script.
+    OnError(bool),
+    /// `echo on|off`: whether each command is printed before its output.
+    Echo(bool),
+    /// `source FILE`: runs the commands of another script.
+    Source(String),
+}
+
+impl Directive {
+    /// Reads the directive of the `line`, `None` if it is a command.
+    pub fn parse(line: &str) -> Option<Result<Directive, String>> {
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+        let directive = match keyword {
+            "set" => match rest.split_once('=') {
+                Some((name, value)) if is_variable_name(name.trim()) => Ok(Directive::Set(name.trim().to_string(), value.trim().to_string())),
+                _ => Err(format!("Invalid variable definition `{line}`, expected `set NAME=VALUE`")),
+            },
+            "on-error" => match rest {
+                "stop" => Ok(Directive::OnError(true)),
+                "continue" => Ok(Directive::OnError(false)),
+                _ => Err(format!("Invalid `{line}`, expected `on-error stop` or `on-error continue`")),
+            },
+            "echo" => match rest {
+                "on" => Ok(Directive::Echo(true)),
+                "off" => Ok(Directive::Echo(false)),
+                _ => Err(format!("Invalid `{line}`, expected `echo on` or `echo off`")),
+            },
+            "source" if !rest.is_empty() => Ok(Directive::Source(rest.to_string())),
+            "source" => Err("Missing the script file, expected `source FILE`".to_string()),
+            _ => return None,
+        };
+        Some(directive)
+    }
+}
+
+/// Checks whether `name` can name a variable: letters, digits and `_`, not
+/// starting with a digit (`$1` is a regex group reference).
+fn is_variable_name(name: &str) -> bool {
+    name.starts_with(|c: char| !c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The state of running scripts, shared by a script and the ones it sources.
+pub struct Script {
+    variables: HashMap<String, String>,
+    pub stop_on_error: bool,
+    pub echo: bool,
+    /// The number of commands (or lines) that failed.
+    pub failures: usize,
+    /// The scripts being run, the innermost last.
+    stack: Vec<PathBuf>,
+}
+
+impl Default for Script {
+    fn default() -> Self {
+        Script {
+            variables: HashMap::new(),
+            stop_on_error: true,
+            echo: false,
+            failures: 0,
+            stack: vec![],
+        }
+    }
+}
+
+impl Script {
+    /// Reads the options of `--script FILE [--continue-on-error] [--echo]`
+    /// (given without `--script` itself), returns the script file with them.
+    pub fn from_cli(cli: &[String]) -> Result<(String, Script), String> {
+        let mut script = Script::default();
+        let mut file = None;
+        for arg in cli {
+            match arg.as_str() {
+                "--continue-on-error" => script.stop_on_error = false,
+                "--echo" => script.echo = true,
+                option if option.starts_with("--") => return Err(format!("Unknown option `{option}` of `--script`")),
+                _ if file.is_some() => return Err(format!("Only one script file is expected, got `{arg}` too")),
+                _ => file = Some(arg.clone()),
+            }
+        }
+        let file = file.ok_or("Missing the script file, expected `--script FILE`")?;
+        Ok((file, script))
+    }
+
+    /// Applies a `set`, `on-error` or `echo` directive.
+    pub fn apply(&mut self, directive: Directive) {
+        match directive {
+            Directive::Set(name, value) => {
+                self.variables.insert(name, value);
+            },
+            Directive::OnError(stop) => self.stop_on_error = stop,
+            Directive::Echo(echo) => self.echo = echo,
+            Directive::Source(_) => {},
+        }
+    }
+
+    /// Replaces the `$NAME` and `${NAME}` variables of the `line` by their
+    /// values, `$$` is a literal `$` and so is a `$` not followed by a name
+    /// (like the `$1` group references of `replace`).
+    pub fn expand(&self, line: &str) -> Result<String, String> {
+        let mut expanded = String::with_capacity(line.len());
+        let mut rest = line;
+        while let Some(dollar) = rest.find('$') {
+            expanded.push_str(&rest[..dollar]);
+            let after = &rest[dollar + 1..];
+            let (name, next) = if let Some(braced) = after.strip_prefix('{') {
+                let end = braced.find('}').ok_or(format!("Unclosed `${{` in `{line}`"))?;
+                (&braced[..end], &braced[end + 1..])
+            } else if let Some(next) = after.strip_prefix('$') {
+                expanded.push('$');
+                rest = next;
+                continue;
+            } else if after.starts_with(|c: char| c.is_ascii_digit()) {
+                expanded.push('$');
+                rest = after;
+                continue;
+            } else {
+                let end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            };
+
+            if name.is_empty() {
+                expanded.push('$');
+            } else {
+                let value = self.variables.get(name).ok_or(format!("Undefined variable `{name}`"))?;
+                expanded.push_str(value);
+            }
+            rest = next;
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+
+    /// Resolves the `file` of a `source` directive, relative to the directory
+    /// of the script sourcing it (or the current one at the top level).
+    pub fn resolve(&self, file: &str) -> PathBuf {
+        match self.stack.last().and_then(|current| current.parent()) {
+            Some(dir) => dir.join(file),
+            None => PathBuf::from(file),
+        }
+    }
+
+    /// Marks the start of the script at `path`, unless it's already being run
+    /// (sourcing itself, directly or not).
+    pub fn enter(&mut self, path: &Path) -> Result<(), String> {
+        let canonical = path.canonicalize().unwrap_or(path.to_path_buf());
+        if self.stack.iter().any(|p| p.canonicalize().unwrap_or(p.clone()) == canonical) {
+            return Err(format!("The script `{}` is already running, it can't be sourced again", path.display()));
+        }
+        self.stack.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Marks the end of the innermost script.
+    pub fn leave(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Records a failure at `location`: it stops the script (as an error) if
+    /// `stop_on_error` is set, else it is reported and the script goes on.
+    pub fn fail(&mut self, location: &str, message: &str) -> Result<(), String> {
+        self.failures += 1;
+        if self.stop_on_error {
+            return Err(format!("{location}: {message}"));
+        }
+        eprintln!("{location}: {message}");
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+/// The options given to a command as `--name` or `--name=value` tokens right
+/// after the command itself, e.g. `csv --trim=both file:data.csv`.
+#[derive(Clone, Default, Debug)]
+pub struct Args {
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    /// Adds a single option token (without its leading `--`) to the list.
+    pub fn push(&mut self, option: &str) {
+        match option.split_once('=') {
+            Some((name, value)) => self.options.push((name.to_string(), Some(value.to_string()))),
+            None => self.options.push((option.to_string(), None)),
+        }
+    }
+
+    /// Returns the value of the last `name` option, if it was given a value.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// Returns the values of all the `name` options in the order they were given.
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.options.iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v.as_deref().unwrap_or(""))
+            .collect()
+    }
+
+    /// Iterates over the options names and values, in the order they were given.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.options.iter().map(|(n, v)| (n.as_str(), v.as_deref()))
+    }
+
+    /// Checks whether the `name` option was given at all (with or without a value).
+    pub fn has(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| n == name)
+    }
+}
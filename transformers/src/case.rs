@@ -0,0 +1,78 @@
+/// The identifier case styles a text can be converted to.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Case {
+    Snake,
+    Kebab,
+    Camel,
+    Pascal,
+    Constant,
+    Dot,
+    Path,
+    Train,
+}
+
+/// Splits the input into its words. Any character that is not a letter or a
+/// digit separates words, and so does a camelCase boundary: an upper case
+/// letter after a lower case one or a digit (`fooBar`, `http2Server`), or the
+/// last upper case letter of an acronym followed by a lower case one
+/// (`HTTPServer`). Digits stick to the word before them (`base64Encode`).
+pub fn words(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut words = vec![];
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            continue;
+        }
+
+        if !word.is_empty() && c.is_uppercase() {
+            // the word isn't empty, so the previous char is part of it.
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(word);
+                word = String::new();
+            }
+        }
+        word.push(c);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Upper cases the first letter of the `word` and lower cases the rest.
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect(),
+        None => String::new(),
+    }
+}
+
+/// Converts a single line of text to the given `case`.
+pub fn convert(s: &str, case: Case) -> String {
+    let words = words(s);
+    let lower = || words.iter().map(|w| w.to_lowercase());
+    let upper = || words.iter().map(|w| w.to_uppercase());
+    let capitalized = || words.iter().map(|w| capitalize(w));
+
+    match case {
+        Case::Snake => lower().collect::<Vec<String>>().join("_"),
+        Case::Kebab => lower().collect::<Vec<String>>().join("-"),
+        Case::Dot => lower().collect::<Vec<String>>().join("."),
+        Case::Path => lower().collect::<Vec<String>>().join("/"),
+        Case::Constant => upper().collect::<Vec<String>>().join("_"),
+        Case::Train => capitalized().collect::<Vec<String>>().join("-"),
+        Case::Pascal => capitalized().collect(),
+        Case::Camel => lower().take(1).chain(capitalized().skip(1)).collect(),
+    }
+}
@@ -0,0 +1,233 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use serde_json::{Map, Number, Value};
+use crate::csv::{split_record, Csv};
+use crate::spreadsheet::CellValue;
+
+/// The structured data formats of the `convert` command.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DataFormat {
+    Json,
+    Yaml,
+    Toml,
+    /// An array of flat records, one per row with the header as keys.
+    Csv,
+}
+
+impl FromStr for DataFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(DataFormat::Json),
+            "yaml" | "yml" => Ok(DataFormat::Yaml),
+            "toml" => Ok(DataFormat::Toml),
+            "csv" => Ok(DataFormat::Csv),
+            _ => Err(format!("Unknown data format `{s}`, expected one of: json, yaml, toml, csv")),
+        }
+    }
+}
+
+impl fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DataFormat::Json => "json",
+            DataFormat::Yaml => "yaml",
+            DataFormat::Toml => "toml",
+            DataFormat::Csv => "csv",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Settings of the `convert` command.
+pub struct ConvertOptions {
+    /// The input format, detected from the input when not given.
+    pub from: Option<DataFormat>,
+    pub to: DataFormat,
+}
+
+/// Guesses the format of the `text`: json and toml are recognized by parsing
+/// them, csv by two or more lines having the same (non-zero) number of commas,
+/// anything else is taken as yaml.
+pub fn detect(text: &str) -> DataFormat {
+    let trimmed = text.trim_start();
+    if (trimmed.starts_with('{') || trimmed.starts_with('[')) && serde_json::from_str::<Value>(text).is_ok() {
+        return DataFormat::Json;
+    }
+    if text.parse::<toml::Table>().is_ok_and(|table| !table.is_empty()) {
+        return DataFormat::Toml;
+    }
+
+    let counts: Vec<usize> = text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| split_record(line).len())
+        .collect();
+    if counts.len() > 1 && counts[0] > 1 && counts.iter().all(|c| *c == counts[0]) {
+        return DataFormat::Csv;
+    }
+    DataFormat::Yaml
+}
+
+/// Parses the json, yaml or toml `text` into a json value, a toml datetime
+/// (which json and yaml lack) becomes a string and is reported in `lossy`.
+pub fn parse(text: &str, format: DataFormat, lossy: &mut Vec<String>) -> Result<Value, Box<dyn Error>> {
+    match format {
+        DataFormat::Json => Ok(serde_json::from_str(text).map_err(|e| format!("Invalid json input: {e}"))?),
+        DataFormat::Yaml => Ok(serde_yaml_ng::from_str(text).map_err(|e| format!("Invalid yaml input: {e}"))?),
+        DataFormat::Toml => {
+            let table: toml::Table = text.parse().map_err(|e| format!("Invalid toml input: {e}"))?;
+            Ok(from_toml(toml::Value::Table(table), "", lossy))
+        },
+        DataFormat::Csv => Err("Csv input is read as a table, not parsed as a value".into()),
+    }
+}
+
+/// Converts the `csv` table into an array of records, cells that look like
+/// numbers or booleans are typed, nulls stay nulls.
+pub fn from_csv(csv: &Csv) -> Value {
+    let records = csv.rows.iter().map(|row| {
+        let record: Map<String, Value> = csv.header.iter().zip(row)
+            .map(|(key, cell)| (key.clone(), cell_value(cell.as_deref())))
+            .collect();
+        Value::Object(record)
+    });
+    Value::Array(records.collect())
+}
+
+/// The typed value of a csv cell.
+fn cell_value(cell: Option<&str>) -> Value {
+    let text = match cell {
+        Some(text) => text,
+        None => return Value::Null,
+    };
+    match (text, CellValue::infer(Some(text))) {
+        ("true", _) => Value::Bool(true),
+        ("false", _) => Value::Bool(false),
+        (_, CellValue::Number(n)) => match text.trim().parse::<i64>() {
+            Ok(i) => Value::from(i),
+            Err(_) => Number::from_f64(n).map_or(Value::String(text.to_string()), Value::Number),
+        },
+        _ => Value::String(text.to_string()),
+    }
+}
+
+/// Converts a toml value into a json value, `path` is where the value is in
+/// the document (for the lossy conversion reports).
+fn from_toml(value: toml::Value, path: &str, lossy: &mut Vec<String>) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Number::from_f64(f).map_or_else(|| {
+            lossy.push(format!("`{}`: the float {f} has no json equivalent and became null", display_path(path)));
+            Value::Null
+        }, Value::Number),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => {
+            lossy.push(format!("`{}`: the datetime {d} became a string", display_path(path)));
+            Value::String(d.to_string())
+        },
+        toml::Value::Array(items) => Value::Array(items.into_iter().enumerate()
+            .map(|(i, item)| from_toml(item, &format!("{path}[{i}]"), lossy))
+            .collect()),
+        toml::Value::Table(table) => Value::Object(table.into_iter()
+            .map(|(key, item)| {
+                let item = from_toml(item, &format!("{path}.{key}"), lossy);
+                (key, item)
+            })
+            .collect()),
+    }
+}
+
+/// Converts a json value into a toml one, `None` for a null (which toml
+/// lacks) that is then dropped and reported in `lossy`.
+fn to_toml(value: &Value, path: &str, lossy: &mut Vec<String>) -> Option<toml::Value> {
+    match value {
+        Value::Null => {
+            lossy.push(format!("`{}`: null has no toml equivalent and was dropped", display_path(path)));
+            None
+        },
+        Value::Bool(b) => Some(toml::Value::Boolean(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(toml::Value::Integer(i)),
+            None => {
+                if n.is_u64() {
+                    lossy.push(format!("`{}`: the integer {n} is too big for toml and became a float", display_path(path)));
+                }
+                Some(toml::Value::Float(n.as_f64().unwrap_or_default()))
+            },
+        },
+        Value::String(s) => Some(toml::Value::String(s.clone())),
+        Value::Array(items) => Some(toml::Value::Array(items.iter().enumerate()
+            .filter_map(|(i, item)| to_toml(item, &format!("{path}[{i}]"), lossy))
+            .collect())),
+        Value::Object(members) => Some(toml::Value::Table(members.iter()
+            .filter_map(|(key, item)| to_toml(item, &format!("{path}.{key}"), lossy).map(|item| (key.clone(), item)))
+            .collect())),
+    }
+}
+
+/// Converts a json value (an array of records, or a single record) into a
+/// csv table, with the keys of all the records as columns. Nested values are
+/// written as json text and reported in `lossy`.
+pub fn to_csv(value: &Value, lossy: &mut Vec<String>) -> Result<Csv, Box<dyn Error>> {
+    let records: Vec<&Value> = match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![value],
+        _ => return Err("Only an array of records (or a single record) can be converted to csv".into()),
+    };
+
+    let mut header: Vec<String> = vec![];
+    for (i, record) in records.iter().enumerate() {
+        let members = record.as_object().ok_or(format!("The item `[{i}]` isn't a record, only records can be converted to csv"))?;
+        for key in members.keys() {
+            if !header.contains(key) {
+                header.push(key.clone());
+            }
+        }
+    }
+
+    let mut rows = vec![];
+    for (i, record) in records.iter().enumerate() {
+        let members = record.as_object().unwrap();
+        let row = header.iter().map(|key| match members.get(key) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(nested @ (Value::Array(_) | Value::Object(_))) => {
+                lossy.push(format!("`[{i}].{key}`: the nested value was written as json text"));
+                Some(nested.to_string())
+            },
+            Some(scalar) => Some(scalar.to_string()),
+        });
+        rows.push(row.collect());
+    }
+
+    Ok(Csv::new(header, rows, '='))
+}
+
+/// Writes the json `value` in the json, yaml or toml format.
+pub fn write(value: &Value, format: DataFormat, lossy: &mut Vec<String>) -> Result<String, Box<dyn Error>> {
+    match format {
+        DataFormat::Json => Ok(serde_json::to_string_pretty(value)?),
+        DataFormat::Yaml => Ok(serde_yaml_ng::to_string(value)?),
+        DataFormat::Toml => {
+            // a toml document is always a table.
+            let table = match to_toml(value, "", lossy) {
+                Some(toml::Value::Table(table)) => table,
+                Some(other) => {
+                    lossy.push("`.`: the document isn't a table for toml, it was put under the `value` key".to_string());
+                    toml::Table::from_iter([("value".to_string(), other)])
+                },
+                None => toml::Table::new(),
+            };
+            Ok(toml::to_string(&table)?)
+        },
+        DataFormat::Csv => Ok(to_csv(value, lossy)?.to_records()),
+    }
+}
+
+/// Writes the `path` of a value for the reports, `.` is the whole document.
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "." } else { path }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A structure that represent a single row (can be header) in the Csv table.
+pub struct Row<'a> {
+    pub cell_width: usize,
+    pub column_sep: char,
+    pub data: Vec<&'a str>,
+}
+
+impl fmt::Display for Row<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        _ = write!(f, "{}", self.column_sep);
+        for s in &self.data {
+            let diff = self.cell_width - s.len();
+            let mut cell = s.to_string();
+            let mut i = 0;
+            loop {
+                if i == diff {
+                    break;
+                }
+                cell.insert(cell.len(), ' ');
+                i+=1;
+            }
+            _ = write!(f, "{}{}", cell, self.column_sep);
+        }
+        writeln!(f)
+    }
+}
+
+/// Tells which side(s) of a cell get their white spaces trimmed while parsing.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Trim {
+    None,
+    Leading,
+    Both,
+}
+
+impl FromStr for Trim {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Trim::None),
+            "leading" => Ok(Trim::Leading),
+            "both" => Ok(Trim::Both),
+            _ => Err(format!("Unknown trim mode `{s}`, expected one of: none, leading, both")),
+        }
+    }
+}
+
+/// Cleanup rules applied on every cell of the Csv input before it is put in
+/// the table.
+pub struct CellOptions {
+    pub trim: Trim,
+    pub collapse_whitespace: bool,
+    pub null_tokens: Vec<String>,
+}
+
+impl Default for CellOptions {
+    fn default() -> Self {
+        CellOptions {
+            trim: Trim::None,
+            collapse_whitespace: false,
+            null_tokens: vec![],
+        }
+    }
+}
+
+impl CellOptions {
+    /// Applies the cleanup rules on a raw `cell`, returns `None` if the
+    /// cleaned up value is one of the null tokens.
+    pub fn normalize(&self, cell: &str) -> Option<String> {
+        let mut value = match self.trim {
+            Trim::None => cell,
+            Trim::Leading => cell.trim_start(),
+            Trim::Both => cell.trim(),
+        }.to_string();
+
+        if self.collapse_whitespace {
+            let mut collapsed = String::with_capacity(value.len());
+            let mut in_whitespace = false;
+            for c in value.chars() {
+                if c.is_whitespace() {
+                    if !in_whitespace {
+                        collapsed.push(' ');
+                    }
+                    in_whitespace = true;
+                } else {
+                    collapsed.push(c);
+                    in_whitespace = false;
+                }
+            }
+            value = collapsed;
+        }
+
+        if self.null_tokens.contains(&value) {
+            return None;
+        }
+
+        Some(value)
+    }
+}
+
+/// Splits a single csv record into its raw cells. A cell that starts with a
+/// double quote may contain commas and escaped (doubled) double quotes.
+pub fn split_record(line: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c != '"' {
+                cell.push(c);
+            } else if chars.peek() == Some(&'"') {
+                cell.push('"');
+                chars.next();
+            } else {
+                quoted = false;
+            }
+        } else if c == '"' && cell.is_empty() {
+            quoted = true;
+        } else if c == ',' {
+            cells.push(cell);
+            cell = String::new();
+        } else {
+            cell.push(c);
+        }
+    }
+    cells.push(cell);
+
+    cells
+}
+
+/// Quotes the `cell` (if needed) so that it's read back as is by `split_record`.
+fn quote(cell: &str) -> String {
+    if cell.contains(',') || cell.starts_with('"') {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// A structure that encapsulate the logic for building a Cvs table, cells 
+/// with `None` values are nulls and rendered as empty cells.
+pub struct Csv {
+    pub header: Vec<String>,
+    pub header_sep: char,
+    pub rows: Vec<Vec<Option<String>>>,
+    max_cell_width: usize,
+    table_width: usize,
+}
+
+impl Csv {
+    /// Construct and initialize a new instance from Csv.
+    pub fn new(header: Vec<String>, rows: Vec<Vec<Option<String>>>, header_sep: char) -> Csv {
+        let mut csv = Csv {
+            header,
+            rows,
+            header_sep,
+            max_cell_width: 0,
+            table_width: 0,
+        };
+
+        csv.init();
+
+        csv
+    }
+
+    /// Writes the table back as csv records (header first), nulls are 
+    /// written as empty cells.
+    pub fn to_records(&self) -> String {
+        let mut records = String::new();
+        let header: Vec<String> = self.header.iter().map(|s| quote(s)).collect();
+        records.push_str(&header.join(","));
+        records.push('\n');
+
+        for row in &self.rows {
+            let row: Vec<String> = row.iter().map(|s| quote(s.as_deref().unwrap_or(""))).collect();
+            records.push_str(&row.join(","));
+            records.push('\n');
+        }
+
+        records
+    }
+
+    fn init(&mut self) {
+        // calculate the max cell width
+        let mut max_cell_width: usize = 0;
+
+        for cell in &self.header {
+            if max_cell_width > cell.len() {
+                continue;
+            }
+            max_cell_width = cell.len();
+        }
+
+        for row in &self.rows {
+            for cell in row.iter().flatten() {
+                if max_cell_width > cell.len() {
+                    continue;
+                }
+                max_cell_width = cell.len();
+            }
+        }
+        self.max_cell_width = max_cell_width;
+        self.table_width = (max_cell_width * self.header.len()) + self.header.len() + 1;
+    }
+}
+
+impl fmt::Display for Csv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {        
+        // rendering the header top line
+        for _ in 1..=self.table_width {
+            _ = write!(f, "{}", self.header_sep);
+        }
+        _ = writeln!(f);
+
+        // rendering the header row
+        let data = self.header.iter().map(|s| s.as_str()).collect();
+        let r = Row { cell_width: self.max_cell_width, column_sep: '|', data };
+        _ = r.fmt(f);
+        
+        // rendering the header bottom line
+        for _ in 1..=self.table_width {
+            _ = write!(f, "{}", self.header_sep);
+        }
+        _ = writeln!(f);
+
+        // rendering the data rows
+        for row in &self.rows {
+            let data = row.iter().map(|s| s.as_deref().unwrap_or("")).collect();
+            let r = Row { cell_width: self.max_cell_width, column_sep: '|', data };
+            _ = r.fmt(f);
+        }
+
+        // rendering the table bottom line
+        for _ in 1..=self.table_width {
+            _ = write!(f, "{}", self.header_sep);
+        }
+        _ = writeln!(f);
+        Ok(())
+    }
+}
\ No newline at end of file
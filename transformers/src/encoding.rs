@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use data_encoding::{Encoding, BASE32, BASE32_NOPAD, BASE64, BASE64URL, BASE64URL_NOPAD, BASE64_NOPAD, HEXLOWER, HEXLOWER_PERMISSIVE};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// The characters percent-encoding leaves as is, the unreserved characters of
+/// RFC 3986 (letters, digits and `-._~`).
+const URL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// The text encodings supported by the `encode` and `decode` commands.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Base64,
+    /// The URL and file name safe base64 alphabet (`-` and `_` instead of `+` and `/`).
+    Base64Url,
+    Base32,
+    Hex,
+    /// Percent-encoding, as used in URL paths and query strings.
+    Url,
+    /// HTML entities (`&amp;`, `&#39;`, ...).
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "base64" => Ok(Format::Base64),
+            "base64url" => Ok(Format::Base64Url),
+            "base32" => Ok(Format::Base32),
+            "hex" => Ok(Format::Hex),
+            "url" => Ok(Format::Url),
+            "html" => Ok(Format::Html),
+            _ => Err(format!("Unknown encoding `{s}`, expected one of: base64, base64url, base32, hex, url, html")),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Base64 => "base64",
+            Format::Base64Url => "base64url",
+            Format::Base32 => "base32",
+            Format::Hex => "hex",
+            Format::Url => "url",
+            Format::Html => "html",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Settings of the `encode` and `decode` commands.
+pub struct EncodingOptions {
+    pub format: Format,
+    /// Leaves out the trailing `=` padding of base64 and base32 output.
+    pub no_pad: bool,
+    /// Encodes spaces as `+` (and decodes `+` as a space) like HTML forms do,
+    /// for the `url` format only.
+    pub form: bool,
+}
+
+impl Default for EncodingOptions {
+    fn default() -> Self {
+        EncodingOptions {
+            format: Format::Base64,
+            no_pad: false,
+            form: false,
+        }
+    }
+}
+
+/// The binary-to-text encoding of the `format`, `None` for the ones that
+/// encode text (url and html).
+fn binary_encoding(format: Format, padded: bool) -> Option<Encoding> {
+    match (format, padded) {
+        (Format::Base64, true) => Some(BASE64),
+        (Format::Base64, false) => Some(BASE64_NOPAD),
+        (Format::Base64Url, true) => Some(BASE64URL),
+        (Format::Base64Url, false) => Some(BASE64URL_NOPAD),
+        (Format::Base32, true) => Some(BASE32),
+        (Format::Base32, false) => Some(BASE32_NOPAD),
+        (Format::Hex, _) => Some(HEXLOWER),
+        _ => None,
+    }
+}
+
+/// Encodes the text `s` (its UTF-8 bytes for the binary encodings).
+pub fn encode(s: &str, options: &EncodingOptions) -> String {
+    if let Some(encoding) = binary_encoding(options.format, !options.no_pad) {
+        return encoding.encode(s.as_bytes());
+    }
+
+    match options.format {
+        Format::Url if options.form => s.split(' ')
+            .map(|part| utf8_percent_encode(part, URL_ENCODE_SET).to_string())
+            .collect::<Vec<String>>()
+            .join("+"),
+        Format::Url => utf8_percent_encode(s, URL_ENCODE_SET).to_string(),
+        _ => html_escape::encode_safe(s).into_owned(),
+    }
+}
+
+/// Checks that every `%` of the percent-encoded `s` is followed by two hex digits.
+fn check_percent_escapes(s: &str) -> Result<(), String> {
+    let bytes = s.as_bytes();
+    for (i, b) in bytes.iter().enumerate() {
+        if *b == b'%' && !bytes.get(i + 1..i + 3).is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+            let escape: String = s[i..].chars().take(3).collect();
+            return Err(format!("Invalid percent escape `{escape}` at position {}, expected `%` followed by two hex digits", i + 1));
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the encoded `s` back into text, white spaces around (and inside,
+/// for the binary encodings) the input are ignored.
+pub fn decode(s: &str, options: &EncodingOptions) -> Result<String, Box<dyn Error>> {
+    let format = options.format;
+    if format == Format::Hex || binary_encoding(format, true).is_some() {
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        // both padded and unpadded input is accepted.
+        let encoding = match format {
+            Format::Hex => HEXLOWER_PERMISSIVE,
+            _ => binary_encoding(format, compact.ends_with('=')).unwrap(),
+        };
+        let bytes = encoding.decode(compact.as_bytes())
+            .map_err(|e| format!("Invalid {format} input: {e}"))?;
+        return String::from_utf8(bytes)
+            .map_err(|e| format!("The decoded data isn't UTF-8 text (invalid byte at position {})", e.utf8_error().valid_up_to() + 1).into());
+    }
+
+    let s = s.trim();
+    match format {
+        Format::Url => {
+            check_percent_escapes(s)?;
+            let s = if options.form { s.replace('+', " ") } else { s.to_string() };
+            let text = percent_decode_str(&s).decode_utf8()
+                .map_err(|e| format!("The decoded data isn't UTF-8 text (invalid byte at position {})", e.valid_up_to() + 1))?;
+            Ok(text.into_owned())
+        },
+        _ => Ok(html_escape::decode_html_entities(s).into_owned()),
+    }
+}
@@ -0,0 +1,206 @@
+use std::error::Error;
+use std::str::FromStr;
+use crate::csv::{CellOptions, Csv};
+
+/// The side of a fixed-width cell its value is aligned to.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+impl FromStr for Align {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Align::Left),
+            "right" => Ok(Align::Right),
+            _ => Err(format!("Unknown alignment `{s}`, expected one of: left, right")),
+        }
+    }
+}
+
+/// Layout settings of fixed-width records.
+pub struct FixedOptions {
+    pub widths: Option<Vec<usize>>,
+    pub pad: char,
+    pub align: Align,
+    pub gap: Option<usize>,
+}
+
+impl Default for FixedOptions {
+    fn default() -> Self {
+        FixedOptions {
+            widths: None,
+            pad: ' ',
+            align: Align::Left,
+            gap: None,
+        }
+    }
+}
+
+/// Parses a `W1:W2:...` list of column widths.
+pub fn parse_widths(s: &str) -> Result<Vec<usize>, String> {
+    s.split(':')
+        .map(|w| match w.trim().parse::<usize>() {
+            Ok(w) if w > 0 => Ok(w),
+            _ => Err(format!("Invalid column width `{w}`, widths should be positive numbers like `10:5:8`")),
+        })
+        .collect()
+}
+
+/// Guesses the column widths out of the white space alignment of the `lines`:
+/// a position that is blank (white space or the `pad` character) in all the
+/// lines separates columns. Left aligned columns start right after a blank
+/// run and right aligned ones end right before it, so the blanks on the edges
+/// of the lines never make up a column of their own.
+pub fn infer_widths(lines: &[&str], pad: char, align: Align) -> Vec<usize> {
+    let lines: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
+    let line_width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let blank: Vec<bool> = (0..line_width)
+        .map(|i| lines.iter().all(|l| l.get(i).is_none_or(|c| c.is_whitespace() || *c == pad)))
+        .collect();
+    let first = blank.iter().position(|b| !b).unwrap_or(0);
+    let last = blank.iter().rposition(|b| !b).unwrap_or(0);
+
+    let mut widths = vec![];
+    let mut start = 0;
+    for i in (first + 1)..=last {
+        let boundary = match align {
+            Align::Left => blank[i - 1] && !blank[i],
+            Align::Right => !blank[i - 1] && blank[i],
+        };
+        if boundary {
+            widths.push(i - start);
+            start = i;
+        }
+    }
+    if line_width > start {
+        widths.push(line_width - start);
+    }
+
+    widths
+}
+
+/// Cuts a single fixed-width `line` into its cells, the last column takes
+/// whatever is left of the line.
+fn split_line(line: &str, widths: &[usize]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut cells = vec![];
+    let mut start = 0;
+
+    for (i, width) in widths.iter().enumerate() {
+        let end = if i == widths.len() - 1 { chars.len() } else { start + width };
+        let cell: String = chars.iter().take(end).skip(start).collect();
+        cells.push(cell);
+        start += width;
+    }
+
+    cells
+}
+
+/// Parses fixed-width records (the first one is the header) into a `Csv`
+/// table. Without `widths` the columns are inferred from the alignment, the
+/// `pad` character is stripped from the padded side of each cell (based on
+/// `align`) before the `cells` options are applied.
+pub fn parse(input: &str, options: &FixedOptions, cells: &CellOptions) -> Result<Csv, Box<dyn Error>> {
+    let lines: Vec<&str> = input.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return Err("No fixed-width records found in the input".into());
+    }
+
+    let widths = match &options.widths {
+        Some(widths) => widths.clone(),
+        None => infer_widths(&lines, options.pad, options.align),
+    };
+
+    let mut records = lines.iter().map(|line| {
+        split_line(line, &widths).iter()
+            .map(|cell| match options.align {
+                Align::Left => cells.normalize(cell.trim_end_matches(options.pad)),
+                Align::Right => cells.normalize(cell.trim_start_matches(options.pad)),
+            })
+            .collect::<Vec<Option<String>>>()
+    });
+
+    let header = records.next().unwrap().into_iter().map(|s| s.unwrap_or_default()).collect();
+    let rows = records.collect();
+
+    Ok(Csv::new(header, rows, '='))
+}
+
+/// Renders the `csv` table (header first) as fixed-width records padded with
+/// the `pad` character. Without `widths` each column is as wide as its widest
+/// cell, columns are separated by `gap` pad characters (defaults to 1 for
+/// computed widths and 0 for given ones).
+pub fn render(csv: &Csv, options: &FixedOptions) -> Result<String, Box<dyn Error>> {
+    let (pad, align) = (options.pad, options.align);
+    let gap = options.gap.unwrap_or(if options.widths.is_some() { 0 } else { 1 });
+    let widths = match &options.widths {
+        Some(widths) => {
+            if widths.len() != csv.header.len() {
+                return Err(format!("Got {} column widths for a table of {} columns", widths.len(), csv.header.len()).into());
+            }
+            widths.clone()
+        },
+        None => {
+            let mut widths: Vec<usize> = csv.header.iter().map(|h| h.chars().count()).collect();
+            for row in &csv.rows {
+                for (i, cell) in row.iter().enumerate() {
+                    widths[i] = widths[i].max(cell.as_deref().unwrap_or("").chars().count());
+                }
+            }
+            widths
+        },
+    };
+
+    let mut output = String::new();
+    let header = csv.header.iter().map(|s| s.as_str());
+    let rows = csv.rows.iter().map(|row| row.iter().map(|s| s.as_deref().unwrap_or("")));
+    let records = std::iter::once(header.collect::<Vec<&str>>()).chain(rows.map(|r| r.collect()));
+
+    for (line_number, record) in records.enumerate() {
+        for (i, cell) in record.iter().enumerate() {
+            let length = cell.chars().count();
+            if length > widths[i] {
+                return Err(format!("Cell `{cell}` at line {line_number} is longer than its column width ({})", widths[i]).into());
+            }
+            if i > 0 {
+                output.extend(std::iter::repeat_n(pad, gap));
+            }
+            let padding = std::iter::repeat_n(pad, widths[i] - length);
+            match align {
+                Align::Left => {
+                    output.push_str(cell);
+                    output.extend(padding);
+                },
+                Align::Right => {
+                    output.extend(padding);
+                    output.push_str(cell);
+                },
+            }
+        }
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_right_aligned_records() {
+        let input = "   name  age\n    bob   42\n  alice    7\n";
+        let options = FixedOptions { align: Align::Right, ..FixedOptions::default() };
+        let csv = parse(input, &options, &CellOptions::default()).unwrap();
+
+        assert_eq!(csv.header, vec!["name", "age"]);
+        assert_eq!(csv.rows, vec![
+            vec![Some("bob".to_string()), Some("42".to_string())],
+            vec![Some("alice".to_string()), Some("7".to_string())],
+        ]);
+    }
+}
@@ -0,0 +1,93 @@
+use std::error::Error;
+use regex::{Regex, RegexBuilder};
+
+/// Settings of a grep-style line search.
+pub struct GrepOptions {
+    pub pattern: String,
+    pub ignore_case: bool,
+    /// Selects the lines that don't match instead.
+    pub invert: bool,
+    /// The number of context lines printed before each selected line.
+    pub before: usize,
+    /// The number of context lines printed after each selected line.
+    pub after: usize,
+    /// Prints the matched parts of the lines (or one of their capture groups)
+    /// instead of the whole lines.
+    pub only_matching: bool,
+    pub group: Option<String>,
+    /// Prints the number of selected lines only.
+    pub count: bool,
+}
+
+/// The matched text of a line, or of the capture `group` when one is given.
+fn extract(regex: &Regex, line: &str, group: Option<&str>) -> Vec<String> {
+    regex.captures_iter(line)
+        .filter_map(|captures| match group {
+            Some(group) => match group.parse::<usize>() {
+                Ok(index) => captures.get(index),
+                Err(_) => captures.name(group),
+            },
+            None => captures.get(0),
+        })
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+/// Searches the lines of `s` for the pattern, returns the output with the
+/// number of selected lines. Selected lines are prefixed with their number and
+/// a `:`, context lines with their number and a `-`, and non-adjacent groups
+/// of lines are separated by a `--` line.
+pub fn grep(s: &str, options: &GrepOptions) -> Result<(String, usize), Box<dyn Error>> {
+    let regex = RegexBuilder::new(&options.pattern)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .map_err(|e| format!("Invalid pattern `{}`: {e}", options.pattern))?;
+    if let Some(group) = &options.group {
+        let known = match group.parse::<usize>() {
+            Ok(index) => index < regex.captures_len(),
+            Err(_) => regex.capture_names().any(|name| name == Some(group.as_str())),
+        };
+        if !known {
+            return Err(format!("The pattern `{}` has no capture group `{group}`", options.pattern).into());
+        }
+    }
+
+    let lines: Vec<&str> = s.lines().collect();
+    let selected: Vec<bool> = lines.iter().map(|line| regex.is_match(line) != options.invert).collect();
+    let count = selected.iter().filter(|s| **s).count();
+    if options.count {
+        return Ok((count.to_string(), count));
+    }
+
+    let mut output: Vec<String> = vec![];
+    // the index right after the last printed line, to know when a separator is needed.
+    let mut printed_until: Option<usize> = None;
+    for (i, line) in lines.iter().enumerate() {
+        if !selected[i] {
+            continue;
+        }
+
+        let first = i.saturating_sub(options.before).max(printed_until.unwrap_or(0));
+        let last = (i + options.after).min(lines.len() - 1);
+        if printed_until.is_some_and(|until| first > until) {
+            output.push("--".to_string());
+        }
+
+        for j in first..=last {
+            // a selected line in the context of another one is printed when its own turn comes.
+            if j > i && selected[j] {
+                break;
+            }
+            if j != i {
+                output.push(format!("{}-{}", j + 1, lines[j]));
+            } else if options.only_matching && !options.invert {
+                output.extend(extract(&regex, line, options.group.as_deref()).iter().map(|m| format!("{}:{m}", i + 1)));
+            } else {
+                output.push(format!("{}:{line}", i + 1));
+            }
+            printed_until = Some(j + 1);
+        }
+    }
+
+    Ok((output.join("\n"), count))
+}
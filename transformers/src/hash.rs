@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::str::FromStr;
+use data_encoding::{BASE64, HEXLOWER, HEXLOWER_PERMISSIVE};
+use sha2::Digest;
+
+/// The size of the chunks files are hashed by, so they're never loaded whole.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The hash and checksum algorithms of the `hash` command.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+    Blake3,
+    Crc32,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "md5" => Ok(Algorithm::Md5),
+            "sha1" => Ok(Algorithm::Sha1),
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha512" => Ok(Algorithm::Sha512),
+            "blake3" => Ok(Algorithm::Blake3),
+            "crc32" => Ok(Algorithm::Crc32),
+            _ => Err(format!("Unknown algorithm `{s}`, expected one of: md5, sha1, sha256, sha512, blake3, crc32")),
+        }
+    }
+}
+
+/// The text encodings of a digest.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DigestEncoding {
+    Hex,
+    Base64,
+}
+
+impl FromStr for DigestEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hex" => Ok(DigestEncoding::Hex),
+            "base64" => Ok(DigestEncoding::Base64),
+            _ => Err(format!("Unknown digest encoding `{s}`, expected one of: hex, base64")),
+        }
+    }
+}
+
+/// Settings of the `hash` command.
+pub struct HashOptions {
+    pub algorithm: Algorithm,
+    pub encoding: DigestEncoding,
+    /// The expected digest (in either encoding) to compare the computed one to.
+    pub verify: Option<String>,
+}
+
+impl Default for HashOptions {
+    fn default() -> Self {
+        HashOptions {
+            algorithm: Algorithm::Sha256,
+            encoding: DigestEncoding::Hex,
+            verify: None,
+        }
+    }
+}
+
+/// A running hash computation of one of the algorithms.
+enum Hasher {
+    Md5(md5::Md5),
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Hasher {
+        match algorithm {
+            Algorithm::Md5 => Hasher::Md5(md5::Md5::new()),
+            Algorithm::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            Algorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            Algorithm::Sha512 => Hasher::Sha512(sha2::Sha512::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(data),
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Blake3(h) => { h.update(data); },
+            Hasher::Crc32(h) => h.update(data),
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Md5(h) => h.finalize().to_vec(),
+            Hasher::Sha1(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Sha512(h) => h.finalize().to_vec(),
+            Hasher::Blake3(h) => h.finalize().as_bytes().to_vec(),
+            // the checksum is written big-endian, like the `crc32` tools print it.
+            Hasher::Crc32(h) => h.finalize().to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// Computes the digest of the `text`.
+pub fn hash_text(text: &str, algorithm: Algorithm) -> Vec<u8> {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(text.as_bytes());
+    hasher.finalize()
+}
+
+/// Computes the digest of the file at `path`, which is read chunk by chunk.
+pub fn hash_file(path: &str, algorithm: Algorithm) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut file = File::open(path).map_err(|e| format!("Unable to open the file `{path}`: {e}"))?;
+    let mut hasher = Hasher::new(algorithm);
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Unable to read the file `{path}`: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Writes the `digest` in the given encoding.
+pub fn encode_digest(digest: &[u8], encoding: DigestEncoding) -> String {
+    match encoding {
+        DigestEncoding::Hex => HEXLOWER.encode(digest),
+        DigestEncoding::Base64 => BASE64.encode(digest),
+    }
+}
+
+/// Checks whether the `expected` digest (hex in any case, or base64) is the
+/// same as the computed `digest`.
+pub fn matches(digest: &[u8], expected: &str) -> bool {
+    let expected = expected.trim();
+    [HEXLOWER_PERMISSIVE.decode(expected.as_bytes()), BASE64.decode(expected.as_bytes())]
+        .into_iter()
+        .any(|decoded| decoded.is_ok_and(|bytes| bytes == digest))
+}
@@ -0,0 +1,239 @@
+use std::error::Error;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{Serializer, Value};
+
+/// Settings of the `json` command.
+pub struct JsonOptions {
+    /// The indentation of a nesting level in pretty-printed output.
+    pub indent: String,
+    pub minify: bool,
+    /// Only checks the input, reporting where it's invalid.
+    pub validate: bool,
+    pub sort_keys: bool,
+    /// A path expression selecting the values to print (see `Step`).
+    pub query: Option<String>,
+    /// Prints the selected strings without their quotes.
+    pub raw: bool,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        JsonOptions {
+            indent: "  ".to_string(),
+            minify: false,
+            validate: false,
+            sort_keys: false,
+            query: None,
+            raw: false,
+        }
+    }
+}
+
+/// A single step of a path expression, which is written either jq-like
+/// (`.items[0].name`, `.[]`) or JSONPath-like (`$.items[*]['name']`, `$..id`).
+#[derive(Debug, PartialEq)]
+enum Step {
+    /// `.name`, `."name"` or `['name']`: the member of an object.
+    Key(String),
+    /// `[N]`: an array item, negative indexes count from the end.
+    Index(i64),
+    /// `[]`, `[*]` or `.*`: all the items of an array or members of an object.
+    All,
+    /// `..`: the value and all its descendants.
+    Descend,
+}
+
+/// Parses the JSON `text`, pointing at the location of the error if invalid.
+fn parse(text: &str) -> Result<Value, String> {
+    serde_json::from_str(text).map_err(|e| {
+        let message = e.to_string();
+        let message = message.split(" at line ").next().unwrap_or_default();
+        let line = text.lines().nth(e.line().saturating_sub(1)).unwrap_or_default();
+        let caret = " ".repeat(e.column().saturating_sub(1)) + "^";
+        format!("Invalid JSON at line {}, column {}: {message}\n{line}\n{caret}", e.line(), e.column())
+    })
+}
+
+/// Checks whether `c` can be part of an unquoted member name.
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Reads a quoted name starting right after its opening `quote`, returns it
+/// with the index right after its closing quote.
+fn read_quoted(chars: &[char], start: usize, quote: char, query: &str) -> Result<(String, usize), String> {
+    let mut name = String::new();
+    let mut i = start;
+    while i < chars.len() && chars[i] != quote {
+        // a backslash escapes the next character, like a quote in the name.
+        if chars[i] == '\\' && i + 1 < chars.len() {
+            i += 1;
+        }
+        name.push(chars[i]);
+        i += 1;
+    }
+    if i == chars.len() {
+        return Err(format!("Unclosed quote at position {} of the query `{query}`", start));
+    }
+    Ok((name, i + 1))
+}
+
+/// Parses a path expression into its steps.
+fn parse_query(query: &str) -> Result<Vec<Step>, String> {
+    let chars: Vec<char> = query.trim().chars().collect();
+    let unexpected = |i: usize| format!("Unexpected `{}` at position {} of the query `{query}`", chars[i], i + 1);
+    let mut steps = vec![];
+    let mut i = if chars.first() == Some(&'$') { 1 } else { 0 };
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' if chars.get(i + 1) == Some(&'.') => {
+                steps.push(Step::Descend);
+                i += 2;
+                // `..name` is a shorthand of `..` followed by `.name`.
+                if chars.get(i).is_some_and(|c| is_name_char(*c) || *c == '*') {
+                    i -= 1;
+                }
+            },
+            '.' => {
+                i += 1;
+                match chars.get(i) {
+                    Some('*') => {
+                        steps.push(Step::All);
+                        i += 1;
+                    },
+                    Some('"') => {
+                        let (name, next) = read_quoted(&chars, i + 1, '"', query)?;
+                        steps.push(Step::Key(name));
+                        i = next;
+                    },
+                    Some(c) if is_name_char(*c) => {
+                        let start = i;
+                        while i < chars.len() && is_name_char(chars[i]) {
+                            i += 1;
+                        }
+                        steps.push(Step::Key(chars[start..i].iter().collect()));
+                    },
+                    // a lone `.` (the whole value) or the `.` of `.[0]`.
+                    None | Some('[') => {},
+                    Some(_) => return Err(unexpected(i)),
+                }
+            },
+            '[' => {
+                i += 1;
+                match chars.get(i) {
+                    Some(']') => steps.push(Step::All),
+                    Some('*') if chars.get(i + 1) == Some(&']') => {
+                        steps.push(Step::All);
+                        i += 1;
+                    },
+                    Some(quote @ ('"' | '\'')) => {
+                        let (name, next) = read_quoted(&chars, i + 1, *quote, query)?;
+                        steps.push(Step::Key(name));
+                        i = next;
+                    },
+                    Some(c) if c.is_ascii_digit() || *c == '-' => {
+                        let start = i;
+                        i += 1;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                        let index: String = chars[start..i].iter().collect();
+                        let index = index.parse().map_err(|_| format!("Invalid index `{index}` in the query `{query}`"))?;
+                        steps.push(Step::Index(index));
+                    },
+                    Some(_) => return Err(unexpected(i)),
+                    None => return Err(format!("Unclosed `[` at the end of the query `{query}`")),
+                }
+                if chars.get(i) != Some(&']') {
+                    return match chars.get(i) {
+                        Some(_) => Err(unexpected(i)),
+                        None => Err(format!("Unclosed `[` at the end of the query `{query}`")),
+                    };
+                }
+                i += 1;
+            },
+            _ => return Err(unexpected(i)),
+        }
+    }
+    Ok(steps)
+}
+
+/// Pushes the `value` and all its descendants (depth first) to `output`.
+fn descendants<'a>(value: &'a Value, output: &mut Vec<&'a Value>) {
+    output.push(value);
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| descendants(item, output)),
+        Value::Object(members) => members.values().for_each(|member| descendants(member, output)),
+        _ => {},
+    }
+}
+
+/// Selects the values the `steps` lead to out of the `root` value, the steps
+/// that don't apply to a value (like a key on an array) just select nothing.
+fn select<'a>(root: &'a Value, steps: &[Step]) -> Vec<&'a Value> {
+    let mut values = vec![root];
+    for step in steps {
+        let mut next = vec![];
+        for value in values {
+            match (step, value) {
+                (Step::Key(key), Value::Object(members)) => next.extend(members.get(key)),
+                (Step::Index(index), Value::Array(items)) => {
+                    let position = if *index < 0 { items.len() as i64 + index } else { *index };
+                    if position >= 0 {
+                        next.extend(items.get(position as usize));
+                    }
+                },
+                (Step::All, Value::Array(items)) => next.extend(items),
+                (Step::All, Value::Object(members)) => next.extend(members.values()),
+                (Step::Descend, _) => descendants(value, &mut next),
+                _ => {},
+            }
+        }
+        values = next;
+    }
+    values
+}
+
+/// Writes the `value` out, pretty-printed unless minified.
+fn write(value: &Value, options: &JsonOptions) -> Result<String, Box<dyn Error>> {
+    if options.raw {
+        if let Value::String(s) = value {
+            return Ok(s.clone());
+        }
+    }
+    if options.minify {
+        return Ok(serde_json::to_string(value)?);
+    }
+
+    let mut output = vec![];
+    let mut serializer = Serializer::with_formatter(&mut output, PrettyFormatter::with_indent(options.indent.as_bytes()));
+    value.serialize(&mut serializer)?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Formats (or validates, or queries) the JSON `text` with the given `options`.
+pub fn format(text: &str, options: &JsonOptions) -> Result<String, Box<dyn Error>> {
+    let mut value = parse(text)?;
+    if options.validate {
+        return Ok("The input is valid JSON.".to_string());
+    }
+    if options.sort_keys {
+        value.sort_all_objects();
+    }
+
+    let selected = match &options.query {
+        Some(query) => {
+            let selected = select(&value, &parse_query(query)?);
+            if selected.is_empty() {
+                return Err(format!("No value matches the query `{query}`").into());
+            }
+            selected
+        },
+        None => vec![&value],
+    };
+
+    let written = selected.into_iter().map(|v| write(v, options)).collect::<Result<Vec<String>, _>>()?;
+    Ok(written.join("\n"))
+}
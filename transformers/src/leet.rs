@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fs;
+use crate::password::random_below;
+
+/// A leetspeak substitution table, each (lower case) letter with the
+/// substitutes it can be replaced with.
+pub type LeetMap = Vec<(char, Vec<String>)>;
+
+/// Settings of the leetspeak substitution.
+#[derive(Default)]
+pub struct LeetOptions {
+    pub table: LeetMap,
+    /// Picks a random substitute for each letter instead of the first one.
+    pub random: bool,
+}
+
+impl LeetOptions {
+    /// Adds the entries of a substitution table, an entry replaces the one
+    /// added before it for the same letter.
+    pub fn add_table(&mut self, map: LeetMap) {
+        for (letter, substitutes) in map {
+            match self.table.iter_mut().find(|(l, _)| *l == letter) {
+                Some(entry) => entry.1 = substitutes,
+                None => self.table.push((letter, substitutes)),
+            }
+        }
+    }
+
+    /// The substitutes of the letter `c`, whatever its case.
+    fn substitutes(&self, c: char) -> Option<&Vec<String>> {
+        let letter = lower_letter(c);
+        self.table.iter().find(|(l, _)| *l == letter).map(|(_, subs)| subs)
+    }
+}
+
+/// Lower cases the letter `c`, a letter that doesn't lower case to a single
+/// letter is kept as is.
+fn lower_letter(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+/// Parses the letter of a table entry, it's stored lower cased so entries
+/// match both cases.
+fn parse_letter(letter: &str) -> Option<char> {
+    let mut chars = letter.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(lower_letter(c)),
+        _ => None,
+    }
+}
+
+/// Parses a substitution table, each line holds a letter followed by its
+/// substitutes, all separated by white spaces. Empty lines and `#` comments
+/// are skipped.
+pub fn parse_table(text: &str) -> Result<LeetMap, String> {
+    let mut map = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let from = parts.next().unwrap();
+        let letter = parse_letter(from)
+            .ok_or(format!("Invalid substitution at line {}: `{from}` should be a single letter", i + 1))?;
+        let substitutes: Vec<String> = parts.map(|s| s.to_string()).collect();
+        if substitutes.is_empty() {
+            return Err(format!("Invalid substitution at line {}: `{line}`, expected `LETTER SUBSTITUTE...`", i + 1));
+        }
+        map.push((letter, substitutes));
+    }
+    Ok(map)
+}
+
+/// Parses an inline substitution like `a:@/4`, a letter and its substitutes
+/// separated by slashes.
+pub fn parse_inline(entry: &str) -> Result<LeetMap, String> {
+    let invalid = || format!("Invalid substitution `{entry}`, expected `LETTER:SUB1/SUB2/...`");
+    let (from, substitutes) = entry.split_once(':').ok_or_else(invalid)?;
+    let letter = parse_letter(from).ok_or_else(invalid)?;
+    let substitutes: Vec<String> = substitutes.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect();
+    if substitutes.is_empty() {
+        return Err(invalid());
+    }
+    Ok(vec![(letter, substitutes)])
+}
+
+/// Loads one of the bundled substitution tables (`basic` or `full`), or a
+/// table file at the given path.
+pub fn load_table(name: &str) -> Result<LeetMap, String> {
+    match name {
+        "basic" => parse_table(include_str!("leet/basic.txt")),
+        "full" => parse_table(include_str!("leet/full.txt")),
+        path => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read the substitution table `{path}`: {e}"))?;
+            parse_table(&text)
+        },
+    }
+}
+
+/// Replaces the letters of `s` that have substitutes in the table, letters
+/// are matched case insensitively.
+pub fn substitute(s: &str, options: &LeetOptions) -> Result<String, Box<dyn Error>> {
+    let mut output = String::with_capacity(s.len());
+    for c in s.chars() {
+        match options.substitutes(c) {
+            Some(substitutes) => {
+                let index = if options.random { random_below(substitutes.len())? } else { 0 };
+                output.push_str(&substitutes[index]);
+            },
+            None => output.push(c),
+        }
+    }
+    Ok(output)
+}
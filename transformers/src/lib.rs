@@ -0,0 +1,618 @@
+//! Text transformations (case conversions, slugs, passwords, encodings,
+//! hashes, json, csv tables and spreadsheets...) shared by the transformer
+//! binaries.
+//!
+//! A transformation is run by its command name with `transform`, or through a
+//! `Registry` which also holds the commands added by the application:
+//!
+//! ```
+//! use transformers::{transform, Args};
+//!
+//! let mut args = Args::default();
+//! args.push("sep=_");
+//! let output = transform("Hello World", "slugify", &args).unwrap();
+//! assert_eq!(output.text, "hello_world");
+//! ```
+
+use std::fmt;
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use crate::case::Case;
+use crate::convert::{ConvertOptions, DataFormat};
+use crate::csv::{split_record, CellOptions, Csv};
+use crate::encoding::EncodingOptions;
+use crate::fixed::{parse_widths, FixedOptions};
+use crate::grep::GrepOptions;
+use crate::hash::HashOptions;
+use crate::json::JsonOptions;
+use crate::leet::LeetOptions;
+use crate::locale::Locale;
+use crate::password::{PassphraseOptions, PasswordOptions};
+use crate::registry::Command;
+use crate::replace::ReplaceOptions;
+use crate::slug::SlugOptions;
+use crate::title::TitleStyle;
+
+pub use crate::args::Args;
+pub use crate::registry::{ArgSpec, Registry, Transform};
+
+mod args;
+mod case;
+mod convert;
+pub mod csv;
+mod encoding;
+mod fixed;
+mod grep;
+mod hash;
+mod json;
+mod leet;
+mod locale;
+mod password;
+mod registry;
+mod replace;
+mod slug;
+mod strength;
+mod title;
+mod spreadsheet;
+
+/// Transformer struct, used to transorm input `String` into another by
+/// chaining multiple calls of its functions to form a pipeline for the
+/// transformation.
+struct Transformer {
+    data: String,
+    summary: Option<String>,
+}
+
+/// The result of a transformation: the transformed text, and a short summary
+/// of what was done for the commands that report one (like `replace`).
+pub struct Output {
+    pub text: String,
+    pub summary: Option<String>,
+}
+
+impl fmt::Display for Transformer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.data)
+    }
+}
+
+#[allow(clippy::wrong_self_convention)]
+impl Transformer {
+    /// Parses the entire string input into a `Csv` table, cleaning up each
+    /// cell with the given `cells` options.
+    fn parse_csv(&self, cells: &CellOptions) -> Result<Csv, Box<dyn Error>> {
+        let mut header_vec: Vec<String> = vec![];
+        let mut rows_matrix: Vec<Vec<Option<String>>> = vec![];
+        let mut first_line = true;
+        let mut column_count: usize = 0;
+        let lines: Vec<&str> = self.data.split('\n').collect();
+        let mut line_number = 0;
+        for line in lines {
+            // skip empty lines
+            if line.is_empty() {
+                continue;
+            }
+            let line_splitted: Vec<Option<String>> = split_record(line).iter().map(|s| cells.normalize(s)).collect();
+            if first_line {
+                header_vec = line_splitted.into_iter().map(|s| s.unwrap_or_default()).collect();
+                column_count = header_vec.len();
+                first_line = false;
+            }
+            else {
+                if line_splitted.len() != column_count {
+                    return Err(format!("Mismatched columns count at line {line_number}, (all rows should have {column_count} columns)").into());
+                }
+                rows_matrix.push(line_splitted);
+            }
+            line_number += 1;
+        }
+
+        Ok(Csv::new(header_vec, rows_matrix, '='))
+    }
+
+    /// Transforms the entire string input into a `Csv` table, cleaning up each
+    /// cell with the given `cells` options.
+    fn to_csv(mut self, cells: &CellOptions) -> Result<Transformer, Box<dyn Error>> {
+        let csv = self.parse_csv(cells)?;
+        self.data = csv.to_string();
+
+        Ok(self)
+    }
+
+    /// Exports the input (as a `Csv` table) to the spreadsheet file at `path`.
+    fn to_spreadsheet(mut self, cells: &CellOptions, path: &str) -> Result<Transformer, Box<dyn Error>> {
+        let csv = self.parse_csv(cells)?;
+        spreadsheet::export(&csv, path)?;
+        self.data = format!("Exported {} rows to `{path}`.", csv.rows.len());
+
+        Ok(self)
+    }
+
+    /// Transforms fixed-width records into a `Csv` table.
+    fn from_fixed(mut self, layout: &FixedOptions, cells: &CellOptions) -> Result<Transformer, Box<dyn Error>> {
+        let csv = fixed::parse(&self.data, layout, cells)?;
+        self.data = csv.to_string();
+
+        Ok(self)
+    }
+
+    /// Transforms the input (as a `Csv` table) into fixed-width records.
+    fn to_fixed(mut self, layout: &FixedOptions, cells: &CellOptions) -> Result<Transformer, Box<dyn Error>> {
+        let csv = self.parse_csv(cells)?;
+        self.data = fixed::render(&csv, layout)?;
+
+        Ok(self)
+    }
+
+    /// Lower cases the input following the casing rules of the `locale`.
+    fn to_lower(mut self, locale: Locale) -> Result<Transformer, Box<dyn Error>> {
+        self.data = locale::to_lower(&self.data, locale);
+        Ok(self)
+    }
+
+    /// Upper cases the input following the casing rules of the `locale`.
+    fn to_upper(mut self, locale: Locale) -> Result<Transformer, Box<dyn Error>> {
+        self.data = locale::to_upper(&self.data, locale);
+        Ok(self)
+    }
+
+    /// Case folds the input for case-insensitive comparisons.
+    fn fold(mut self, locale: Locale) -> Result<Transformer, Box<dyn Error>> {
+        self.data = locale::fold(&self.data, locale);
+        Ok(self)
+    }
+
+    /// Removes all spaces from the input string.
+    fn with_no_spaces(mut self) -> Result<Transformer, Box<dyn Error>> {
+        self.data = self.data.replace(" ", "");
+        Ok(self)
+    }
+
+    /// Replaces the matches of a regex pattern in the input, the number of
+    /// replacements is reported in the summary.
+    fn replace(mut self, options: &ReplaceOptions) -> Result<Transformer, Box<dyn Error>> {
+        let (output, count) = replace::replace(&self.data, options)?;
+        self.data = output;
+        self.summary = Some(format!("Replacements: {count}"));
+        Ok(self)
+    }
+
+    /// Keeps the input lines matching a regex pattern (with their context), the
+    /// number of selected lines is reported in the summary.
+    fn grep(mut self, options: &GrepOptions) -> Result<Transformer, Box<dyn Error>> {
+        let (output, count) = grep::grep(&self.data, options)?;
+        self.data = output;
+        self.summary = Some(format!("Selected lines: {count}"));
+        Ok(self)
+    }
+
+    /// Encodes the input with the given `options`.
+    fn encode(mut self, options: &EncodingOptions) -> Result<Transformer, Box<dyn Error>> {
+        self.data = encoding::encode(&self.data, options);
+        Ok(self)
+    }
+
+    /// Decodes the input with the given `options`.
+    fn decode(mut self, options: &EncodingOptions) -> Result<Transformer, Box<dyn Error>> {
+        self.data = encoding::decode(&self.data, options)?;
+        Ok(self)
+    }
+
+    /// Replaces the input with its digest.
+    fn hash(self, options: &HashOptions) -> Result<Transformer, Box<dyn Error>> {
+        let digest = hash::hash_text(&self.data, options.algorithm);
+        self.with_digest(digest, options)
+    }
+
+    /// Replaces the input with the digest of the file at `path`, which is
+    /// streamed instead of loaded.
+    fn hash_file(self, path: &str, options: &HashOptions) -> Result<Transformer, Box<dyn Error>> {
+        let digest = hash::hash_file(path, options.algorithm)?;
+        self.with_digest(digest, options)
+    }
+
+    /// Replaces the input with the encoded `digest`. When an expected digest
+    /// is given, a mismatch is an error and a match is reported in the summary.
+    fn with_digest(mut self, digest: Vec<u8>, options: &HashOptions) -> Result<Transformer, Box<dyn Error>> {
+        self.data = hash::encode_digest(&digest, options.encoding);
+
+        if let Some(expected) = &options.verify {
+            if !hash::matches(&digest, expected) {
+                return Err(format!("Digest mismatch: expected `{expected}`, got `{}`", self.data).into());
+            }
+            self.summary = Some("Verified: the digest matches the expected one".to_string());
+        }
+        Ok(self)
+    }
+
+    /// Pretty-prints, minifies, validates or queries the JSON input with the
+    /// given `options`.
+    fn to_json(mut self, options: &JsonOptions) -> Result<Transformer, Box<dyn Error>> {
+        self.data = json::format(&self.data, options)?;
+        Ok(self)
+    }
+
+    /// Converts the structured data input from a format to another, the input
+    /// format is detected if not given. The lossy conversions (like a null
+    /// dropped from toml) are reported in the summary.
+    fn convert(mut self, options: &ConvertOptions, cells: &CellOptions) -> Result<Transformer, Box<dyn Error>> {
+        let from = options.from.unwrap_or_else(|| convert::detect(&self.data));
+        let mut lossy = vec![];
+        let value = match from {
+            DataFormat::Csv => convert::from_csv(&self.parse_csv(cells)?),
+            _ => convert::parse(&self.data, from, &mut lossy)?,
+        };
+        self.data = convert::write(&value, options.to, &mut lossy)?;
+
+        let mut summary = format!("Converted from {from} to {}", options.to);
+        if !lossy.is_empty() {
+            summary.push_str(&format!(", with {} lossy conversion(s):", lossy.len()));
+            for report in lossy {
+                summary.push_str(&format!("\n  - {report}"));
+            }
+        }
+        self.summary = Some(summary);
+        Ok(self)
+    }
+
+    /// Converts the input into a slug with the given `options`.
+    fn slugify(mut self, options: &SlugOptions) -> Result<Transformer, Box<dyn Error>> {
+        self.data = slug::slugify(&self.data, options);
+        Ok(self)
+    }
+
+    /// Converts the first letter of each word in the string to upper case. 
+    fn to_pascal_case(mut self) -> Result<Transformer, Box<dyn Error>> {
+        let chars: Vec<char> = self.data.chars().collect();
+        let mut output = String::new();
+        let mut new_word = true;
+
+        for mut c in chars {
+
+            // return error if character is not ascii
+            if !c.is_ascii() {
+                return Err(format!("Letters has to be ascii, {c} detected!").into());
+            }
+            if new_word {
+                c = c.to_ascii_uppercase();
+            }
+            new_word = c == ' ';
+            output.push(c);
+        }
+
+        self.data = output;
+
+        Ok(self)
+    }
+
+    /// Title cases each line of the input following the `style` guide and the
+    /// casing rules of the `locale`.
+    fn to_title(mut self, style: TitleStyle, locale: Locale) -> Result<Transformer, Box<dyn Error>> {
+        self.data = title::title_case(&self.data, style, locale);
+        Ok(self)
+    }
+
+    /// Converts each line of the input to the given identifier `case`.
+    fn to_case(mut self, case: Case) -> Result<Transformer, Box<dyn Error>> {
+        self.data = self.data.lines()
+            .map(|line| case::convert(line, case))
+            .collect::<Vec<String>>()
+            .join("\n");
+        Ok(self)
+    }
+
+    /// Replaces the input with a random password (or a passphrase if the
+    /// `phrase` options are given) and its entropy estimate.
+    fn to_random_password(mut self, options: &PasswordOptions, phrase: Option<&PassphraseOptions>) -> Result<Transformer, Box<dyn Error>> {
+        let (password, entropy) = match phrase {
+            Some(phrase) => password::generate_passphrase(phrase)?,
+            None => password::generate_password(options)?,
+        };
+        self.data = format!("{password}\n\nEntropy: ~{entropy:.0} bits");
+        Ok(self)
+    }
+
+    /// Replaces the input password with a report of its strength.
+    fn to_strength_report(mut self) -> Result<Transformer, Box<dyn Error>> {
+        let password = self.data.trim_end_matches(['\r', '\n']);
+        self.data = strength::analyze(password).to_string();
+        Ok(self)
+    }
+
+    /// Replace the letters of the input string that have a leetspeak substitute
+    /// (whatever their case) to make the whole input looks like a password.
+    fn passwordify(mut self, options: &LeetOptions) -> Result<Transformer, Box<dyn Error>> {
+        self.data = leet::substitute(&self.data, options)?;
+        Ok(self)
+    }
+}
+
+/// Builds the Csv cells cleanup options out of the command arguments.
+fn cell_options(args: &Args) -> Result<CellOptions, Box<dyn Error>> {
+    let mut options = CellOptions::default();
+    if let Some(trim) = args.get("trim") {
+        options.trim = trim.parse()?;
+    }
+    options.collapse_whitespace = args.has("collapse");
+    options.null_tokens = args.get_all("null").into_iter().map(|s| s.to_string()).collect();
+    Ok(options)
+}
+
+/// Reads the `--locale` option, defaults to the root locale.
+fn locale(args: &Args) -> Result<Locale, Box<dyn Error>> {
+    match args.get("locale") {
+        Some(locale) => Ok(locale.parse()?),
+        None => Ok(Locale::Root),
+    }
+}
+
+/// Builds the slug options out of the command arguments.
+fn slug_options(args: &Args) -> Result<SlugOptions, Box<dyn Error>> {
+    let mut options = SlugOptions::default();
+    if let Some(sep) = args.get("sep") {
+        options.separator = match sep {
+            "-" | "_" | "." => sep.chars().next().unwrap(),
+            _ => return Err(format!("Unsupported separator `{sep}`, expected one of: - _ .").into()),
+        };
+    }
+    if let Some(max) = args.get("max") {
+        options.max_length = Some(max.parse().map_err(|_| format!("Invalid max length `{max}`, expected a number"))?);
+    }
+    options.keep_case = args.has("keep-case");
+    if args.has("stop-words") {
+        options.add_default_stop_words();
+    }
+    options.stop_words.extend(args.get_all("stop").into_iter().map(|s| s.to_string()));
+    for name in args.get_all("translit") {
+        options.add_translit(slug::load_translit(name)?);
+    }
+    Ok(options)
+}
+
+/// Parses the numeric option `name`, if given.
+fn number_option(args: &Args, name: &str) -> Result<Option<usize>, Box<dyn Error>> {
+    match args.get(name) {
+        Some(value) => Ok(Some(value.parse().map_err(|_| format!("Invalid --{name} `{value}`, expected a number"))?)),
+        None => Ok(None),
+    }
+}
+
+/// Builds the random password options out of the command arguments.
+fn password_options(args: &Args) -> Result<PasswordOptions, Box<dyn Error>> {
+    let mut options = PasswordOptions::default();
+    if let Some(length) = number_option(args, "length")? {
+        options.length = length;
+    }
+    if let Some(classes) = args.get("classes") {
+        options.classes = classes.split(':').map(|c| c.parse()).collect::<Result<_, _>>()?;
+    }
+    Ok(options)
+}
+
+/// Builds the passphrase options out of the command arguments.
+fn passphrase_options(args: &Args) -> Result<PassphraseOptions, Box<dyn Error>> {
+    let mut options = PassphraseOptions::default();
+    if let Some(words) = number_option(args, "words")? {
+        options.words = words;
+    }
+    if let Some(sep) = args.get("sep") {
+        options.separator = sep.to_string();
+    }
+    options.capitalize = args.has("capitalize");
+    Ok(options)
+}
+
+/// Builds the leetspeak substitution options out of the command arguments,
+/// the bundled `basic` table is used when no table is given.
+fn leet_options(args: &Args) -> Result<LeetOptions, Box<dyn Error>> {
+    let mut options = LeetOptions::default();
+    let tables = args.get_all("table");
+    if tables.is_empty() {
+        options.add_table(leet::load_table("basic")?);
+    }
+    for name in tables {
+        options.add_table(leet::load_table(name)?);
+    }
+    for sub in args.get_all("sub") {
+        options.add_table(leet::parse_inline(sub)?);
+    }
+    options.random = args.has("random");
+    Ok(options)
+}
+
+/// Builds the find-and-replace options out of the command arguments.
+fn replace_options(args: &Args) -> Result<ReplaceOptions, Box<dyn Error>> {
+    let pattern = args.get("pattern").ok_or("Missing the `--pattern=REGEX` option")?;
+    Ok(ReplaceOptions {
+        pattern: pattern.to_string(),
+        replacement: args.get("with").unwrap_or("").to_string(),
+        ignore_case: args.has("ignore-case"),
+        multiline: args.has("multiline"),
+        first_only: args.has("first"),
+    })
+}
+
+/// Builds the grep options out of the command arguments.
+fn grep_options(args: &Args) -> Result<GrepOptions, Box<dyn Error>> {
+    let pattern = args.get("pattern").ok_or("Missing the `--pattern=REGEX` option")?;
+    let context = number_option(args, "context")?.unwrap_or(0);
+    Ok(GrepOptions {
+        pattern: pattern.to_string(),
+        ignore_case: args.has("ignore-case"),
+        invert: args.has("invert"),
+        before: number_option(args, "before")?.unwrap_or(context),
+        after: number_option(args, "after")?.unwrap_or(context),
+        only_matching: args.has("only-matching") || args.has("group"),
+        group: args.get("group").map(|g| g.to_string()),
+        count: args.has("count"),
+    })
+}
+
+/// Builds the encode / decode options out of the command arguments.
+fn encoding_options(args: &Args) -> Result<EncodingOptions, Box<dyn Error>> {
+    let mut options = EncodingOptions::default();
+    if let Some(format) = args.get("format") {
+        options.format = format.parse()?;
+    }
+    options.no_pad = args.has("no-pad");
+    options.form = args.has("form");
+    Ok(options)
+}
+
+/// Builds the hash options out of the command arguments.
+fn hash_options(args: &Args) -> Result<HashOptions, Box<dyn Error>> {
+    let mut options = HashOptions::default();
+    if let Some(algorithm) = args.get("algo") {
+        options.algorithm = algorithm.parse()?;
+    }
+    if let Some(encoding) = args.get("encoding") {
+        options.encoding = encoding.parse()?;
+    }
+    options.verify = args.get("verify").map(|v| v.to_string());
+    Ok(options)
+}
+
+/// The widest indentation of pretty-printed json.
+const MAX_JSON_INDENT: usize = 16;
+
+/// Builds the json options out of the command arguments.
+fn json_options(args: &Args) -> Result<JsonOptions, Box<dyn Error>> {
+    let mut options = JsonOptions::default();
+    match args.get("indent") {
+        Some("tab") => options.indent = "\t".to_string(),
+        Some(_) => {
+            let indent = number_option(args, "indent")?.unwrap_or(2);
+            if indent > MAX_JSON_INDENT {
+                return Err(format!("The indentation can't be more than {MAX_JSON_INDENT} spaces, got {indent}").into());
+            }
+            options.indent = " ".repeat(indent);
+        },
+        None => {},
+    }
+    options.minify = args.has("minify");
+    options.validate = args.has("validate");
+    options.sort_keys = args.has("sort-keys");
+    options.query = args.get("query").map(|q| q.to_string());
+    options.raw = args.has("raw");
+    Ok(options)
+}
+
+/// Builds the convert options out of the command arguments.
+fn convert_options(args: &Args) -> Result<ConvertOptions, Box<dyn Error>> {
+    let to = args.get("to").ok_or("Missing the `--to=FORMAT` option for the output format")?;
+    Ok(ConvertOptions {
+        from: args.get("from").map(|f| f.parse()).transpose()?,
+        to: to.parse()?,
+    })
+}
+
+/// Builds the fixed-width layout options out of the command arguments.
+fn fixed_options(args: &Args) -> Result<FixedOptions, Box<dyn Error>> {
+    let mut options = FixedOptions::default();
+    if let Some(widths) = args.get("widths") {
+        options.widths = Some(parse_widths(widths)?);
+    }
+    if let Some(pad) = args.get("pad") {
+        let mut chars = pad.chars();
+        options.pad = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(format!("The padding should be a single character, got `{pad}`").into()),
+        };
+    }
+    if let Some(align) = args.get("align") {
+        options.align = align.parse()?;
+    }
+    if let Some(gap) = args.get("gap") {
+        options.gap = Some(gap.parse().map_err(|_| format!("Invalid gap `{gap}`, expected a number"))?);
+    }
+    Ok(options)
+}
+
+/// A helper method that calls the `Transformer` with different settings based 
+/// on the `command` (and its `args`) to transform the `input` string.
+fn run_command(input: String, command: Command, args: &Args) -> Result<Output, Box<dyn Error>> {
+    let t = Transformer { data: input, summary: None };
+
+    let t = match command {
+        Command::Lower if args.has("fold") => t.fold(locale(args)?)?,
+        Command::Lower => t.to_lower(locale(args)?)?,
+        Command::Upper => t.to_upper(locale(args)?)?,
+        Command::Nospace => t.with_no_spaces()?,
+        Command::Slugify => t.slugify(&slug_options(args)?)?,
+        Command::Replace => t.replace(&replace_options(args)?)?,
+        Command::Grep => t.grep(&grep_options(args)?)?,
+        Command::Encode => t.encode(&encoding_options(args)?)?,
+        Command::Decode => t.decode(&encoding_options(args)?)?,
+        Command::Hash => t.hash(&hash_options(args)?)?,
+        Command::Json => t.to_json(&json_options(args)?)?,
+        Command::Convert => t.convert(&convert_options(args)?, &cell_options(args)?)?,
+        Command::Title => {
+            let style = args.get("style").unwrap_or("chicago").parse()?;
+            t.to_title(style, locale(args)?)?
+        },
+        Command::Pascal => t.to_case(Case::Pascal)?,
+        Command::Snake => t.to_case(Case::Snake)?,
+        Command::Kebab => t.to_case(Case::Kebab)?,
+        Command::Camel => t.to_case(Case::Camel)?,
+        Command::Constant => t.to_case(Case::Constant)?,
+        Command::Dot => t.to_case(Case::Dot)?,
+        Command::Path => t.to_case(Case::Path)?,
+        Command::Train => t.to_case(Case::Train)?,
+        Command::Password => {
+            let phrase = if args.has("phrase") { Some(passphrase_options(args)?) } else { None };
+            t.to_random_password(&password_options(args)?, phrase.as_ref())?
+        },
+        Command::Strength => t.to_strength_report()?,
+        Command::Passwordify => t.to_pascal_case()?.with_no_spaces()?.passwordify(&leet_options(args)?)?,
+        Command::Csv => t.to_csv(&cell_options(args)?)?,
+        Command::Export => {
+            let path = args.get("out").ok_or("Missing the `--out=PATH` option for the output file")?;
+            t.to_spreadsheet(&cell_options(args)?, path)?
+        },
+        Command::Fixed => t.from_fixed(&fixed_options(args)?, &cell_options(args)?)?,
+        Command::ToFixed => t.to_fixed(&fixed_options(args)?, &cell_options(args)?)?,
+        _ => return Err(format!("Un-recoginzed transformation method: [{:?}]", command).into()),
+    };
+
+    Ok(Output { text: t.data, summary: t.summary })
+}
+
+/// Runs the `command` (and its `args`) on the file at `path`, for the commands
+/// reading their files by themselves.
+fn run_file_command(path: &str, command: Command, args: &Args) -> Result<Output, Box<dyn Error>> {
+    let t = Transformer { data: String::new(), summary: None };
+
+    let t = match command {
+        Command::Hash => t.hash_file(path, &hash_options(args)?)?,
+        _ => return Err(format!("The [{:?}] transformation doesn't read files by itself", command).into()),
+    };
+
+    Ok(Output { text: t.data, summary: t.summary })
+}
+
+/// Transforms the `input` with the built-in command typed as `name` (like
+/// `slugify` or `csv`) and its `args`, which are checked against the options
+/// of the command.
+pub fn transform(input: &str, name: &str, args: &Args) -> Result<Output, Box<dyn Error>> {
+    let registry = Registry::with_builtins();
+    let command = registry.get(name).ok_or(format!("Un-recoginzed transformation method: [{name}]"))?;
+    Registry::check_args(command, args)?;
+    registry.run(name, input.to_string(), args)
+}
+
+/// Reads the input file at `path`, spreadsheet workbooks (.xlsx/.xlsm/.xlsb/
+/// .xls/.ods) are loaded as csv records out of the given `sheet` (a name or a
+/// 1-based index; default: the first sheet).
+pub fn read_file(path: &str, sheet: Option<&str>) -> Result<String, Box<dyn Error>> {
+    if spreadsheet::is_workbook(path) {
+        let csv = spreadsheet::import(path, sheet)?;
+        return Ok(csv.to_records());
+    }
+
+    let mut file = File::open(path).map_err(|e| format!("Unable to open the file `{path}`: {e}"))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).map_err(|e| format!("Unable to read the file `{path}`: {e}"))?;
+
+    Ok(content)
+}
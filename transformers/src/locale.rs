@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+/// The locales that have their own casing rules, every other locale uses the
+/// default (root) Unicode casing.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Locale {
+    Root,
+    /// Turkish and Azeri, where `i` and `ı` are two different letters.
+    Turkic,
+    /// Lithuanian, where the dot of `i` and `j` is kept under accents.
+    Lithuanian,
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Parses a locale tag like `tr`, `az-Latn` or `de_DE`, only its language
+    /// part matters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or("").to_lowercase();
+        if language.is_empty() || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Invalid locale `{s}`, expected a language tag like `en`, `tr` or `de-DE`"));
+        }
+
+        match language.as_str() {
+            "tr" | "az" => Ok(Locale::Turkic),
+            "lt" => Ok(Locale::Lithuanian),
+            _ => Ok(Locale::Root),
+        }
+    }
+}
+
+/// Combining dot above, used to spell the dotted capital I in decomposed form.
+const COMBINING_DOT_ABOVE: char = '\u{0307}';
+
+/// Checks whether `c` is an accent placed above the letter (combining class
+/// 230), only the common combining diacritical marks block is covered.
+fn is_above_accent(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{0314}' | '\u{033D}'..='\u{0344}' | '\u{0346}' | '\u{034A}'..='\u{034C}'
+        | '\u{0350}'..='\u{0352}' | '\u{0357}' | '\u{035B}' | '\u{0363}'..='\u{036F}')
+}
+
+/// Checks whether `c` is a soft dotted letter, one that loses its dot when an
+/// accent is placed above it.
+fn is_soft_dotted(c: char) -> bool {
+    matches!(c, 'i' | 'j' | 'į' | 'ɨ' | 'ⁱ' | 'ј' | 'і')
+}
+
+/// Maps the letters with locale specific lower case forms, leaving the rest of
+/// the string to the default rules.
+fn map_lower(s: &str, locale: Locale) -> String {
+    let mut mapped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let accent_follows = chars.peek().is_some_and(|n| is_above_accent(*n));
+        match (locale, c) {
+            (Locale::Turkic, 'İ') => mapped.push('i'),
+            (Locale::Turkic, 'I') if chars.peek() == Some(&COMBINING_DOT_ABOVE) => {
+                chars.next();
+                mapped.push('i');
+            },
+            (Locale::Turkic, 'I') => mapped.push('ı'),
+            // Lithuanian keeps the dot of i and j visible under other accents.
+            (Locale::Lithuanian, 'I') if accent_follows => mapped.push_str("i\u{0307}"),
+            (Locale::Lithuanian, 'J') if accent_follows => mapped.push_str("j\u{0307}"),
+            (Locale::Lithuanian, 'Į') if accent_follows => mapped.push_str("į\u{0307}"),
+            (Locale::Lithuanian, 'Ì') => mapped.push_str("i\u{0307}\u{0300}"),
+            (Locale::Lithuanian, 'Í') => mapped.push_str("i\u{0307}\u{0301}"),
+            (Locale::Lithuanian, 'Ĩ') => mapped.push_str("i\u{0307}\u{0303}"),
+            _ => mapped.push(c),
+        }
+    }
+    mapped
+}
+
+/// Lower cases the string `s` following the casing rules of the `locale`.
+pub fn to_lower(s: &str, locale: Locale) -> String {
+    match locale {
+        Locale::Root => s.to_lowercase(),
+        _ => map_lower(s, locale).to_lowercase(),
+    }
+}
+
+/// Upper cases the string `s` following the casing rules of the `locale`.
+pub fn to_upper(s: &str, locale: Locale) -> String {
+    match locale {
+        Locale::Root => s.to_uppercase(),
+        Locale::Turkic => s.replace('i', "İ").to_uppercase(),
+        Locale::Lithuanian => {
+            // the dot kept above an accented i or j isn't needed in upper case.
+            let mut mapped = String::with_capacity(s.len());
+            let mut after_soft_dotted = false;
+            for c in s.chars() {
+                if c == COMBINING_DOT_ABOVE && after_soft_dotted {
+                    continue;
+                }
+                if is_soft_dotted(c) {
+                    after_soft_dotted = true;
+                } else if !is_above_accent(c) {
+                    after_soft_dotted = false;
+                }
+                mapped.push(c);
+            }
+            mapped.to_uppercase()
+        },
+    }
+}
+
+/// Case folds the string `s` (full folding), so that strings that only differ
+/// in case become equal. Only the Turkic locale has its own folding rules.
+pub fn fold(s: &str, locale: Locale) -> String {
+    match locale {
+        Locale::Turkic => caseless::default_case_fold_str(&map_lower(s, locale)),
+        _ => caseless::default_case_fold_str(s),
+    }
+}
+
+/// Converts the character `c` to its title case form, which is the first
+/// letter of a capitalized word. That's mostly its upper case, except for
+/// the letters that upper case to more than one letter (`ß` becomes `Ss`)
+/// and the digraphs that have their own title case letter (`ǆ` becomes `ǅ`).
+pub fn to_title(c: char, locale: Locale) -> String {
+    match c {
+        'i' if locale == Locale::Turkic => "İ".to_string(),
+        'ß' => "Ss".to_string(),
+        'ǆ' | 'ǅ' | 'Ǆ' => "ǅ".to_string(),
+        'ǉ' | 'ǈ' | 'Ǉ' => "ǈ".to_string(),
+        'ǌ' | 'ǋ' | 'Ǌ' => "ǋ".to_string(),
+        'ǳ' | 'ǲ' | 'Ǳ' => "ǲ".to_string(),
+        'ﬀ' => "Ff".to_string(),
+        'ﬁ' => "Fi".to_string(),
+        'ﬂ' => "Fl".to_string(),
+        _ => c.to_uppercase().collect(),
+    }
+}
@@ -0,0 +1,156 @@
+use std::error::Error;
+use std::str::FromStr;
+
+/// The character classes a random password can be built from.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digits,
+    Symbols,
+}
+
+impl FromStr for CharClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lower" => Ok(CharClass::Lower),
+            "upper" => Ok(CharClass::Upper),
+            "digits" => Ok(CharClass::Digits),
+            "symbols" => Ok(CharClass::Symbols),
+            _ => Err(format!("Unknown character class `{s}`, expected one of: lower, upper, digits, symbols")),
+        }
+    }
+}
+
+impl CharClass {
+    /// The characters of the class.
+    fn alphabet(&self) -> &'static str {
+        match self {
+            CharClass::Lower => "abcdefghijklmnopqrstuvwxyz",
+            CharClass::Upper => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            CharClass::Digits => "0123456789",
+            CharClass::Symbols => "!@#$%^&*()-_=+[]{};:,.<>/?~",
+        }
+    }
+}
+
+/// The longest random password generated.
+const MAX_LENGTH: usize = 1024;
+
+/// The most words of a generated passphrase.
+const MAX_WORDS: usize = 256;
+
+/// Settings of a random password.
+pub struct PasswordOptions {
+    pub length: usize,
+    pub classes: Vec<CharClass>,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        PasswordOptions {
+            length: 20,
+            classes: vec![CharClass::Lower, CharClass::Upper, CharClass::Digits, CharClass::Symbols],
+        }
+    }
+}
+
+/// Settings of a diceware-style passphrase.
+pub struct PassphraseOptions {
+    pub words: usize,
+    pub separator: String,
+    pub capitalize: bool,
+}
+
+impl Default for PassphraseOptions {
+    fn default() -> Self {
+        PassphraseOptions {
+            words: 6,
+            separator: "-".to_string(),
+            capitalize: false,
+        }
+    }
+}
+
+/// Picks a uniformly distributed random number in `0..n` out of the operating
+/// system's CSPRNG, rejecting the values that would bias the modulo.
+pub(crate) fn random_below(n: usize) -> Result<usize, Box<dyn Error>> {
+    let n = n as u64;
+    let zone = u64::MAX - (u64::MAX % n);
+    loop {
+        let value = getrandom::u64().map_err(|e| format!("Unable to get random numbers from the system: {e}"))?;
+        if value < zone {
+            return Ok((value % n) as usize);
+        }
+    }
+}
+
+/// The bundled passphrase word list.
+fn word_list() -> Vec<&'static str> {
+    include_str!("words/passphrase.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Generates a random password, returns it with its entropy (in bits). The
+/// password is guaranteed to contain at least one character of each class,
+/// a class given more than once counts once.
+pub fn generate_password(options: &PasswordOptions) -> Result<(String, f64), Box<dyn Error>> {
+    let mut classes: Vec<CharClass> = vec![];
+    for class in &options.classes {
+        if !classes.contains(class) {
+            classes.push(*class);
+        }
+    }
+    if classes.is_empty() {
+        return Err("At least one character class is needed to generate a password".into());
+    }
+    if options.length > MAX_LENGTH {
+        return Err(format!("A password can't be longer than {MAX_LENGTH} characters, got {}", options.length).into());
+    }
+    if options.length < classes.len() {
+        return Err(format!("A password of {} characters can't hold all the {} character classes", options.length, classes.len()).into());
+    }
+
+    let alphabet: Vec<char> = classes.iter().flat_map(|c| c.alphabet().chars()).collect();
+    let entropy = options.length as f64 * (alphabet.len() as f64).log2();
+
+    // retrying until all the classes show up keeps every accepted password equally likely.
+    loop {
+        let mut password = String::with_capacity(options.length);
+        for _ in 0..options.length {
+            password.push(alphabet[random_below(alphabet.len())?]);
+        }
+        if classes.iter().all(|class| password.chars().any(|c| class.alphabet().contains(c))) {
+            return Ok((password, entropy));
+        }
+    }
+}
+
+/// Generates a diceware-style passphrase out of the bundled word list,
+/// returns it with its entropy (in bits).
+pub fn generate_passphrase(options: &PassphraseOptions) -> Result<(String, f64), Box<dyn Error>> {
+    if options.words == 0 {
+        return Err("A passphrase needs at least one word".into());
+    }
+    if options.words > MAX_WORDS {
+        return Err(format!("A passphrase can't have more than {MAX_WORDS} words, got {}", options.words).into());
+    }
+
+    let list = word_list();
+    let entropy = options.words as f64 * (list.len() as f64).log2();
+    let mut words = Vec::with_capacity(options.words);
+    for _ in 0..options.words {
+        let word = list[random_below(list.len())?];
+        if options.capitalize {
+            words.push(word[..1].to_uppercase() + &word[1..]);
+        } else {
+            words.push(word.to_string());
+        }
+    }
+
+    Ok((words.join(&options.separator), entropy))
+}
@@ -0,0 +1,470 @@
+use std::error::Error;
+use crate::args::Args;
+use crate::csv::Csv;
+use crate::{run_command, run_file_command, Output};
+
+/// The built-in commands, each one is registered as a `Transform` running the
+/// matching transformation chain of `transform`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Command {
+    Lower,
+    Upper,
+    Slugify,
+    Replace,
+    Grep,
+    Encode,
+    Decode,
+    Hash,
+    Json,
+    Convert,
+    Nospace,
+    Title,
+    Pascal,
+    Snake,
+    Kebab,
+    Camel,
+    Constant,
+    Dot,
+    Path,
+    Train,
+    Password,
+    Passwordify,
+    Strength,
+    Csv,
+    Export,
+    Fixed,
+    ToFixed,
+    Help,
+    Exit,
+}
+
+/// The description of a single option a command accepts.
+#[derive(Clone, Debug)]
+pub struct ArgSpec {
+    /// The option name, without its leading `--`.
+    pub name: String,
+    /// How the value is written in the help (like `N` or `left|right`),
+    /// `None` for a flag that takes no value.
+    pub value: Option<String>,
+    /// What the option does, a `\n` starts a new line in the help.
+    pub help: String,
+}
+
+impl ArgSpec {
+    /// An option that takes no value, like `--minify`.
+    pub fn flag(name: &str, help: &str) -> ArgSpec {
+        ArgSpec { name: name.to_string(), value: None, help: help.to_string() }
+    }
+
+    /// An option that takes a value, like `--indent=N`.
+    pub fn value(name: &str, value: &str, help: &str) -> ArgSpec {
+        ArgSpec { name: name.to_string(), value: Some(value.to_string()), help: help.to_string() }
+    }
+
+    /// How the option is written, like `--indent=N`.
+    fn usage(&self) -> String {
+        match &self.value {
+            Some(value) => format!("--{}={value}", self.name),
+            None => format!("--{}", self.name),
+        }
+    }
+}
+
+/// A command of the prompt: its name, help, the options it accepts and the
+/// transformation itself. Besides the built-in ones, commands can be added to
+/// the `Registry` without touching the rest of the application.
+pub trait Transform: Send + Sync {
+    /// The name the command is typed with.
+    fn name(&self) -> &str;
+
+    /// A one line description of the command, listed by `help`.
+    fn description(&self) -> &str;
+
+    /// The options the command accepts, any other option is rejected.
+    fn args(&self) -> Vec<ArgSpec> {
+        vec![]
+    }
+
+    /// Whether the command takes an input (TEXT or file:PATH).
+    fn takes_input(&self) -> bool {
+        true
+    }
+
+    /// Whether the command takes a multi-line (table) input, which is read
+    /// until an empty line is entered.
+    fn reads_lines(&self) -> bool {
+        false
+    }
+
+    /// Whether the command reads its `file:PATH` input by itself (as a
+    /// stream) instead of having the whole file loaded into the payload.
+    fn streams_file(&self) -> bool {
+        false
+    }
+
+    /// The option a bare word sets when the command is a later stage of a
+    /// pipeline (which takes no input of its own), like `sha256` for the
+    /// `--algo` of `... | hash sha256`.
+    fn positional(&self) -> Option<&str> {
+        None
+    }
+
+    /// Transforms the `input` with the given options.
+    fn apply(&self, input: String, args: &Args) -> Result<Output, Box<dyn Error>>;
+
+    /// Transforms the file at `path`, read by the command itself (see
+    /// `streams_file`), with the given options.
+    fn apply_file(&self, path: &str, args: &Args) -> Result<Output, Box<dyn Error>> {
+        _ = (path, args);
+        Err(format!("The `{}` command doesn't read files by itself", self.name()).into())
+    }
+}
+
+/// A built-in command.
+struct Builtin {
+    command: Command,
+    name: &'static str,
+    description: &'static str,
+    args: Vec<ArgSpec>,
+    takes_input: bool,
+    reads_lines: bool,
+    streams_file: bool,
+    positional: Option<&'static str>,
+}
+
+impl Builtin {
+    fn new(command: Command, name: &'static str, description: &'static str, args: Vec<ArgSpec>) -> Builtin {
+        Builtin { command, name, description, args, takes_input: true, reads_lines: false, streams_file: false, positional: None }
+    }
+
+    fn without_input(mut self) -> Builtin {
+        self.takes_input = false;
+        self
+    }
+
+    fn reading_lines(mut self) -> Builtin {
+        self.reads_lines = true;
+        self
+    }
+
+    fn streaming_file(mut self) -> Builtin {
+        self.streams_file = true;
+        self
+    }
+
+    fn taking_positional(mut self, option: &'static str) -> Builtin {
+        self.positional = Some(option);
+        self
+    }
+}
+
+impl Transform for Builtin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn args(&self) -> Vec<ArgSpec> {
+        self.args.clone()
+    }
+
+    fn takes_input(&self) -> bool {
+        self.takes_input
+    }
+
+    fn reads_lines(&self) -> bool {
+        self.reads_lines
+    }
+
+    fn streams_file(&self) -> bool {
+        self.streams_file
+    }
+
+    fn positional(&self) -> Option<&str> {
+        self.positional
+    }
+
+    fn apply(&self, input: String, args: &Args) -> Result<Output, Box<dyn Error>> {
+        run_command(input, self.command, args)
+    }
+
+    fn apply_file(&self, path: &str, args: &Args) -> Result<Output, Box<dyn Error>> {
+        run_file_command(path, self.command, args)
+    }
+}
+
+/// The general notes printed by `help` after the list of commands.
+const HELP_NOTES: &str = include_str!("help.txt");
+
+/// The `--locale` option of the casing commands.
+fn locale_arg() -> ArgSpec {
+    ArgSpec::value("locale", "TAG", "The language casing rules (`tr` and `az` for the dotted and dotless i; `lt` for the\nLithuanian dot above accented i and j).")
+}
+
+/// The regex options shared by `replace` and `grep`.
+fn pattern_args() -> Vec<ArgSpec> {
+    vec![
+        ArgSpec::value("pattern", "REGEX", "The pattern to search for (required)."),
+        ArgSpec::flag("ignore-case", "Matches letters whatever their case."),
+    ]
+}
+
+/// The options of `encode` and `decode`.
+fn encoding_args() -> Vec<ArgSpec> {
+    vec![
+        ArgSpec::value("format", "FORMAT", "base64 / base64url / base32 / hex / url / html (default: base64)."),
+        ArgSpec::flag("no-pad", "(encode only) Leaves out the trailing `=` padding of base64 and base32 (decoding\naccepts both)."),
+        ArgSpec::flag("form", "(url only) Spaces are encoded as `+` (and `+` decoded as a space) like html forms."),
+    ]
+}
+
+/// The cells cleanup options of the commands reading csv input.
+fn cell_args() -> Vec<ArgSpec> {
+    vec![
+        ArgSpec::value("trim", "none|leading|both", "Trims white spaces around cells (default: none)."),
+        ArgSpec::flag("collapse", "Squeezes runs of white spaces inside cells into a single space."),
+        ArgSpec::value("null", "TOKEN", "Cells equal to TOKEN become nulls (repeatable; `--null=` for empty cells)."),
+    ]
+}
+
+/// The layout options of `fixed` and `tofixed`, along with the cells ones.
+fn fixed_args() -> Vec<ArgSpec> {
+    let mut args = cell_args();
+    args.extend([
+        ArgSpec::value("widths", "W1:W2:...", "The columns widths (default: inferred from alignment / widest cell of each column)."),
+        ArgSpec::value("pad", "CHAR", "The padding character (default: space)."),
+        ArgSpec::value("align", "left|right", "The side cells are aligned to (padding is on the other side; default: left)."),
+        ArgSpec::value("gap", "N", "Padding characters between rendered columns (default: 1 or 0 with --widths)."),
+    ]);
+    args
+}
+
+/// The built-in commands, in the order `help` lists them.
+fn builtins() -> Vec<Builtin> {
+    vec![
+        Builtin::new(Command::Lower, "lower", "Transforms all input to lower case.", vec![
+            locale_arg(),
+            ArgSpec::flag("fold", "Case folds the input for case-insensitive comparisons (ß becomes ss)."),
+        ]),
+        Builtin::new(Command::Upper, "upper", "Transforms all input to upper case.", vec![locale_arg()]),
+        Builtin::new(Command::Nospace, "nospace", "Removes all white spaces  from input.", vec![]),
+        Builtin::new(Command::Slugify, "slugify", "Slugifies input.", vec![
+            ArgSpec::value("sep", "-|_|.", "The words separator (default: -)."),
+            ArgSpec::value("max", "N", "Cuts the slug at the last word boundary that fits in N characters."),
+            ArgSpec::flag("keep-case", "Keeps the letters case instead of lower casing them."),
+            ArgSpec::flag("stop-words", "Drops common English stop words (like the / of / and)."),
+            ArgSpec::value("stop", "WORD", "Drops WORD from the slug (repeatable)."),
+            ArgSpec::value("translit", "arabic|cyrillic|PATH", "Transliteration table applied first (repeatable); a PATH is a file of `FROM TO` lines."),
+        ]),
+        Builtin::new(Command::Replace, "replace", "Replaces the matches of a regex pattern and reports how many were replaced.", {
+            let mut args = pattern_args();
+            args.extend([
+                ArgSpec::value("with", "TEXT", "The replacement (default: empty); `$1` or `${name}` insert a capture group."),
+                ArgSpec::flag("multiline", "Makes `^` and `$` match at the start and end of each line."),
+                ArgSpec::flag("first", "Replaces the first match only (default: all of them)."),
+            ]);
+            args
+        }),
+        Builtin::new(Command::Grep, "grep", "Prints the numbered lines matching a regex pattern (with context lines).", {
+            let mut args = pattern_args();
+            args.extend([
+                ArgSpec::flag("invert", "Selects the lines that don't match instead."),
+                ArgSpec::value("before", "N", "Prints N context lines before each selected line (numbered with a `-`)."),
+                ArgSpec::value("after", "N", "Prints N context lines after each selected line (numbered with a `-`)."),
+                ArgSpec::value("context", "N", "Same as --before=N --after=N."),
+                ArgSpec::flag("only-matching", "Prints the matched parts of the lines instead of the whole lines."),
+                ArgSpec::value("group", "N|NAME", "Prints the given capture group of the matches instead (implies --only-matching)."),
+                ArgSpec::flag("count", "Prints the number of selected lines only."),
+            ]);
+            args
+        }),
+        Builtin::new(Command::Encode, "encode", "Encodes input to base64 / base32 / hex / url (percent) / html entities.", encoding_args()).taking_positional("format"),
+        Builtin::new(Command::Decode, "decode", "Decodes input from base64 / base32 / hex / url (percent) / html entities.", encoding_args()).taking_positional("format"),
+        Builtin::new(Command::Hash, "hash", "Computes the digest (hash or checksum) of input; files are streamed.", vec![
+            ArgSpec::value("algo", "ALGORITHM", "md5 / sha1 / sha256 / sha512 / blake3 / crc32 (default: sha256)."),
+            ArgSpec::value("encoding", "hex|base64", "The digest encoding (default: hex)."),
+            ArgSpec::value("verify", "DIGEST", "Fails unless the digest equals DIGEST (hex in any case or base64)."),
+        ]).streaming_file().taking_positional("algo"),
+        Builtin::new(Command::Json, "json", "Pretty-prints / minifies / validates / queries json input.", vec![
+            ArgSpec::value("indent", "N|tab", "The indentation of pretty-printed output (default: 2 spaces, at most 16)."),
+            ArgSpec::flag("minify", "Prints the json on a single line without white spaces."),
+            ArgSpec::flag("validate", "Only checks the input (an error points at the line and column of the problem)."),
+            ArgSpec::flag("sort-keys", "Sorts the keys of all the objects."),
+            ArgSpec::value("query", "PATH", "Prints the values at PATH: jq-like (`.items[0].name` `.items[].id` `.[-1]`) or\nJSONPath-like (`$.items[*]['a key']` `$..id`)."),
+            ArgSpec::flag("raw", "Prints selected strings without their quotes."),
+        ]),
+        Builtin::new(Command::Convert, "convert", "Converts data between json / yaml / toml / csv and reports lossy conversions.", {
+            let mut args = vec![
+                ArgSpec::value("to", "FORMAT", "The output format: json / yaml / toml / csv (required); csv data is an array of flat records."),
+                ArgSpec::value("from", "FORMAT", "The input format (default: detected from the input)."),
+            ];
+            args.extend(cell_args());
+            args
+        }),
+        Builtin::new(Command::Title, "title", "Makes a title out of input.", vec![
+            ArgSpec::value("style", "chicago|ap", "The style guide deciding which small words stay lower case (default: chicago)."),
+            locale_arg(),
+        ]),
+        Builtin::new(Command::Pascal, "pascal", "Transforms input to PascalCase.", vec![]),
+        Builtin::new(Command::Snake, "snake", "Transforms input to snake_case.", vec![]),
+        Builtin::new(Command::Kebab, "kebab", "Transforms input to kebab-case.", vec![]),
+        Builtin::new(Command::Camel, "camel", "Transforms input to camelCase.", vec![]),
+        Builtin::new(Command::Constant, "constant", "Transforms input to CONSTANT_CASE.", vec![]),
+        Builtin::new(Command::Dot, "dot", "Transforms input to dot.case.", vec![]),
+        Builtin::new(Command::Path, "path", "Transforms input to path/case.", vec![]),
+        Builtin::new(Command::Train, "train", "Transforms input to Train-Case.", vec![]),
+        Builtin::new(Command::Password, "password", "Generates a random password (or passphrase) with its entropy.", vec![
+            ArgSpec::value("length", "N", "The password length (default: 20, at most 1024)."),
+            ArgSpec::value("classes", "C1:C2:...", "The character classes to use out of lower/upper/digits/symbols (default: all)."),
+            ArgSpec::flag("phrase", "Generates a diceware-style passphrase out of a bundled 2048 words list instead."),
+            ArgSpec::value("words", "N", "(--phrase only) The number of words (default: 6, at most 256)."),
+            ArgSpec::value("sep", "TEXT", "(--phrase only) The words separator (default: -)."),
+            ArgSpec::flag("capitalize", "(--phrase only) Capitalizes each word."),
+        ]).without_input(),
+        Builtin::new(Command::Passwordify, "passwordify", "Takes a sentence as input and makes it look like a password (not secure!).", vec![
+            ArgSpec::value("table", "basic|full|PATH", "Leetspeak substitution table (repeatable; default: basic); a PATH is a file of\n`LETTER SUBSTITUTE...` lines. Letters match both cases."),
+            ArgSpec::value("sub", "L:S1/S2/...", "Inline substitutes of the letter L (repeatable); overrides the table entry of L."),
+            ArgSpec::flag("random", "Picks a random substitute for each letter instead of the first one."),
+        ]),
+        Builtin::new(Command::Strength, "strength", "Scores a password and reports its guessable patterns and crack time.", vec![]),
+        Builtin::new(Command::Csv, "csv", "Formats valid multi-line csv input into a table.", cell_args()).reading_lines(),
+        Builtin::new(Command::Export, "export", "Exports csv input to a spreadsheet (.xlsx or .ods) given by --out=PATH.", {
+            let mut args = vec![ArgSpec::value("out", "PATH", "The spreadsheet file to write, its extension picks the format (required).")];
+            args.extend(cell_args());
+            args
+        }).reading_lines(),
+        Builtin::new(Command::Fixed, "fixed", "Formats fixed-width records (first one is the header) into a table.", fixed_args()).reading_lines(),
+        Builtin::new(Command::ToFixed, "tofixed", "Transforms csv input into fixed-width records.", fixed_args()).reading_lines(),
+        Builtin::new(Command::Help, "help", "Prints this list you're reading; `help COMMAND` prints the options of COMMAND.", vec![]).without_input(),
+        Builtin::new(Command::Exit, "exit", "Terminate the program", vec![]).without_input(),
+    ]
+}
+
+/// The commands known to the prompt, the built-in ones and the ones added by
+/// the application. Parsing, running and the help all go through it.
+pub struct Registry {
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl Registry {
+    /// A registry holding the built-in commands.
+    pub fn with_builtins() -> Registry {
+        Registry {
+            transforms: builtins().into_iter().map(|b| Box::new(b) as Box<dyn Transform>).collect(),
+        }
+    }
+
+    /// Adds a command, its name shouldn't be taken already.
+    pub fn register(&mut self, transform: Box<dyn Transform>) -> Result<(), String> {
+        if self.get(transform.name()).is_some() {
+            return Err(format!("A command named `{}` is already registered", transform.name()));
+        }
+        self.transforms.push(transform);
+        Ok(())
+    }
+
+    /// The names of the commands, in the order `help` lists them.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.transforms.iter().map(|t| t.name())
+    }
+
+    /// Finds the command typed as `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Transform> {
+        self.transforms.iter().find(|t| t.name() == name).map(|t| t.as_ref())
+    }
+
+    /// Checks the `args` given to `transform` against its options: unknown
+    /// options, values given to flags and options missing their values are
+    /// rejected. `--sheet` is accepted by every command taking an input.
+    pub fn check_args(transform: &dyn Transform, args: &Args) -> Result<(), String> {
+        let specs = transform.args();
+        for (name, value) in args.iter() {
+            if name == "sheet" && transform.takes_input() {
+                continue;
+            }
+            let spec = match specs.iter().find(|spec| spec.name == name) {
+                Some(spec) => spec,
+                None if specs.is_empty() => return Err(format!("Sir.. `{}` takes no options, `--{name}` is not one.", transform.name())),
+                None => {
+                    let known: Vec<String> = specs.iter().map(|spec| format!("--{}", spec.name)).collect();
+                    return Err(format!("Sir.. `--{name}` is not an option of `{}`, expected one of: {}", transform.name(), known.join(", ")));
+                },
+            };
+            match (&spec.value, value) {
+                (Some(_), None) => return Err(format!("Sir.. the option `--{name}` needs a value (`{}`).", spec.usage())),
+                (None, Some(_)) => return Err(format!("Sir.. the option `--{name}` takes no value.")),
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the command typed as `name` on the `input`.
+    pub fn run(&self, name: &str, input: String, args: &Args) -> Result<Output, Box<dyn Error>> {
+        let transform = self.get(name).ok_or(format!("Un-recoginzed transformation method: [{name}]"))?;
+        if name == "help" {
+            return self.help(input.trim());
+        }
+        transform.apply(input, args)
+    }
+
+    /// Runs the command typed as `name` on the file at `path`, which the
+    /// command reads by itself (see `Transform::streams_file`).
+    pub fn run_file(&self, name: &str, path: &str, args: &Args) -> Result<Output, Box<dyn Error>> {
+        let transform = self.get(name).ok_or(format!("Un-recoginzed transformation method: [{name}]"))?;
+        transform.apply_file(path, args)
+    }
+
+    /// How the command is typed, like `lower [OPTIONS] TEXT | file:PATH`.
+    fn usage(transform: &dyn Transform) -> String {
+        let mut usage = transform.name().to_string();
+        if !transform.args().is_empty() {
+            usage.push_str(" [OPTIONS]");
+        }
+        if transform.takes_input() {
+            usage.push_str(" TEXT | file:PATH");
+        }
+        usage
+    }
+
+    /// Lists the commands with the general notes, or the options of the
+    /// `command` if one is given, as a single column table.
+    fn help(&self, command: &str) -> Result<Output, Box<dyn Error>> {
+        let mut lines = vec![];
+        let header = if command.is_empty() {
+            let width = self.transforms.iter().map(|t| Self::usage(t.as_ref()).len()).max().unwrap_or(0) + 2;
+            for t in &self.transforms {
+                lines.push(format!("{:<20}{:<width$}{}", t.name(), Self::usage(t.as_ref()), t.description()));
+            }
+            lines.push(String::new());
+            lines.push("-".repeat(116));
+            lines.extend(HELP_NOTES.lines().map(|line| line.to_string()));
+            format!("{:<20}{:<width$}{}", "Command", "Format", "Description")
+        } else {
+            let transform = self.get(command).ok_or(format!("Unknown command `{command}`, type `help` for the list of commands"))?;
+            lines.push(format!("Usage: {}", Self::usage(transform)));
+            let args = transform.args();
+            if !args.is_empty() {
+                lines.push(String::new());
+                lines.push("Options:".to_string());
+            }
+            for arg in args {
+                let usage = arg.usage();
+                let mut help = arg.help.lines();
+                if usage.len() < 28 {
+                    lines.push(format!("    {usage:<28}{}", help.next().unwrap_or_default()));
+                } else {
+                    lines.push(format!("    {usage}"));
+                }
+                lines.extend(help.map(|line| format!("{:32}{line}", "")));
+            }
+            format!("{} - {}", transform.name(), transform.description())
+        };
+
+        let rows = lines.into_iter().map(|line| vec![Some(line)]).collect();
+        Ok(Output { text: Csv::new(vec![header], rows, '=').to_string(), summary: None })
+    }
+}
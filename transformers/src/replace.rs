@@ -0,0 +1,33 @@
+use std::error::Error;
+use regex::RegexBuilder;
+
+/// Settings of a regex find-and-replace.
+pub struct ReplaceOptions {
+    pub pattern: String,
+    /// The replacement text, `$1` / `${name}` refer to the capture groups
+    /// (`$$` is a literal dollar sign).
+    pub replacement: String,
+    pub ignore_case: bool,
+    /// Makes `^` and `$` match at the start and end of each line.
+    pub multiline: bool,
+    /// Replaces the first match only, instead of all of them.
+    pub first_only: bool,
+}
+
+/// Replaces the matches of the pattern in `s`, returns the new text with the
+/// number of replacements made.
+pub fn replace(s: &str, options: &ReplaceOptions) -> Result<(String, usize), Box<dyn Error>> {
+    let regex = RegexBuilder::new(&options.pattern)
+        .case_insensitive(options.ignore_case)
+        .multi_line(options.multiline)
+        .build()
+        .map_err(|e| format!("Invalid pattern `{}`: {e}", options.pattern))?;
+
+    let limit = if options.first_only { 1 } else { 0 };
+    let mut count = regex.find_iter(s).count();
+    if options.first_only {
+        count = count.min(1);
+    }
+
+    Ok((regex.replacen(s, limit, options.replacement.as_str()).into_owned(), count))
+}
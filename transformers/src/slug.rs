@@ -0,0 +1,208 @@
+use std::fs;
+use deunicode::deunicode_char;
+
+/// A transliteration table, a list of `(from, to)` replacements.
+pub type TranslitMap = Vec<(String, String)>;
+
+/// The stop words dropped from slugs by the `--stop-words` option.
+const STOP_WORDS: [&str; 24] = [
+    "a", "an", "the", "and", "or", "but", "nor", "of", "at", "by", "for", "from",
+    "in", "into", "on", "onto", "to", "with", "is", "are", "was", "be", "as", "it",
+];
+
+/// Settings of the slug generation.
+pub struct SlugOptions {
+    pub separator: char,
+    pub max_length: Option<usize>,
+    pub keep_case: bool,
+    pub stop_words: Vec<String>,
+    pub translit: TranslitMap,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions {
+            separator: '-',
+            max_length: None,
+            keep_case: false,
+            stop_words: vec![],
+            translit: vec![],
+        }
+    }
+}
+
+impl SlugOptions {
+    /// Adds the bundled English stop words to the ones to drop.
+    pub fn add_default_stop_words(&mut self) {
+        self.stop_words.extend(STOP_WORDS.iter().map(|w| w.to_string()));
+    }
+
+    /// Adds the entries of a transliteration table, tables added first win
+    /// when they have the same entry.
+    pub fn add_translit(&mut self, map: TranslitMap) {
+        self.translit.extend(map);
+        // longer entries first, so `shch` like sequences are matched as a whole.
+        self.translit.sort_by_key(|(from, _)| std::cmp::Reverse(from.chars().count()));
+    }
+}
+
+/// Parses a transliteration table, each line holds a `FROM TO` pair separated
+/// by white spaces (`TO` can be omitted to drop `FROM`), a `FROM` may also be
+/// written as a `U+XXXX` code point. Empty lines and `#` comments are skipped.
+pub fn parse_translit(text: &str) -> Result<TranslitMap, String> {
+    let mut map = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let from = parts.next().unwrap();
+        let to = parts.next().unwrap_or("");
+        if parts.next().is_some() {
+            return Err(format!("Invalid transliteration at line {}: `{line}`, expected `FROM TO`", i + 1));
+        }
+
+        let from = match from.strip_prefix("U+") {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()
+                .and_then(char::from_u32)
+                .ok_or(format!("Invalid code point `{from}` at line {}", i + 1))?
+                .to_string(),
+            None => from.to_string(),
+        };
+        map.push((from, to.to_string()));
+    }
+    Ok(map)
+}
+
+/// Loads one of the bundled transliteration tables (`arabic` or `cyrillic`),
+/// or a table file at the given path.
+pub fn load_translit(name: &str) -> Result<TranslitMap, String> {
+    match name {
+        "arabic" => parse_translit(include_str!("translit/arabic.txt")),
+        "cyrillic" => parse_translit(include_str!("translit/cyrillic.txt")),
+        path => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read the transliteration table `{path}`: {e}"))?;
+            parse_translit(&text)
+        },
+    }
+}
+
+/// Upper cases the first letter of `s`.
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Matches the (lower case) `from` at the start of `s` whatever its case,
+/// returns the length (in bytes) of the matched part of `s`.
+fn match_lowercase(s: &str, from: &str) -> Option<usize> {
+    let mut expected = from.chars().peekable();
+    for (i, c) in s.char_indices() {
+        if expected.peek().is_none() {
+            return Some(i);
+        }
+        // a character may lower case into several ones, like `İ`.
+        for lower in c.to_lowercase() {
+            if expected.next() != Some(lower) {
+                return None;
+            }
+        }
+    }
+    expected.peek().is_none().then_some(s.len())
+}
+
+/// Applies the transliteration table on `s`, entries are matched case
+/// insensitively and keep the case of the first replaced letter.
+fn transliterate(s: &str, map: &TranslitMap) -> String {
+    if map.is_empty() {
+        return s.to_string();
+    }
+
+    let mut output = String::with_capacity(s.len());
+    let mut rest = s;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (from, to) in map {
+            if rest.starts_with(from.as_str()) {
+                output.push_str(to);
+                rest = &rest[from.len()..];
+                continue 'outer;
+            }
+        }
+
+        let lower = rest.chars().next().unwrap().to_lowercase().to_string();
+        for (from, to) in map {
+            if !from.starts_with(&lower) {
+                continue;
+            }
+            if let Some(length) = match_lowercase(rest, from) {
+                output.push_str(&capitalize_first(to));
+                rest = &rest[length..];
+                continue 'outer;
+            }
+        }
+
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    output
+}
+
+/// Splits the (transliterated) input into ascii words, anything that isn't an
+/// ascii letter or digit after `deunicode` separates words.
+fn ascii_words(s: &str, keep_case: bool) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    for c in s.chars() {
+        let ascii = if c.is_ascii() { c.to_string() } else { deunicode_char(c).unwrap_or("-").to_string() };
+        for a in ascii.chars() {
+            if a.is_ascii_alphanumeric() {
+                word.push(if keep_case { a } else { a.to_ascii_lowercase() });
+            } else if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Converts `s` into a slug: ascii letters and digits words joined by the
+/// separator, never starting or ending with it.
+pub fn slugify(s: &str, options: &SlugOptions) -> String {
+    let words = ascii_words(&transliterate(s, &options.translit), options.keep_case);
+
+    // dropping the stop words shouldn't leave us with an empty slug.
+    let kept: Vec<String> = words.iter()
+        .filter(|w| !options.stop_words.iter().any(|stop| stop.eq_ignore_ascii_case(w)))
+        .cloned()
+        .collect();
+    let words = if kept.is_empty() { words } else { kept };
+
+    let mut slug = String::new();
+    for word in &words {
+        let extra = if slug.is_empty() { 0 } else { 1 };
+        if let Some(max) = options.max_length {
+            if slug.len() + extra + word.len() > max {
+                // a single word that is too long is cut, otherwise we stop at the last word that fits.
+                if slug.is_empty() {
+                    slug.push_str(&word[..max]);
+                }
+                break;
+            }
+        }
+        if extra > 0 {
+            slug.push(options.separator);
+        }
+        slug.push_str(word);
+    }
+    slug
+}
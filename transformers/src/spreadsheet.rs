@@ -0,0 +1,267 @@
+use std::error::Error;
+use std::path::Path;
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use spreadsheet_ods::{format, CellStyle, Length, Sheet, WorkBook};
+use crate::csv::Csv;
+
+/// The spreadsheet file formats a `Csv` table can be exported to.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SheetFormat {
+    Xlsx,
+    Ods,
+}
+
+impl SheetFormat {
+    /// Picks the format out of the extension of the given file `path`.
+    pub fn from_path(path: &str) -> Result<SheetFormat, String> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+
+        match extension.as_deref() {
+            Some("xlsx") => Ok(SheetFormat::Xlsx),
+            Some("ods") => Ok(SheetFormat::Ods),
+            _ => Err(format!("Unsupported spreadsheet file `{path}`, expected a .xlsx or .ods file")),
+        }
+    }
+}
+
+/// The file extensions of the workbooks that can be read as csv input.
+const WORKBOOK_EXTENSIONS: [&str; 5] = ["xlsx", "xlsm", "xlsb", "xls", "ods"];
+
+/// Checks whether the file at `path` is a spreadsheet workbook (based on its
+/// extension) rather than a plain text file.
+pub fn is_workbook(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| WORKBOOK_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// The typed value of a single cell, inferred from its text.
+#[derive(PartialEq, Debug)]
+pub enum CellValue<'a> {
+    Empty,
+    Number(f64),
+    Date(NaiveDate),
+    Text(&'a str),
+}
+
+impl<'a> CellValue<'a> {
+    /// Infers the type of the `cell`, nulls and blank cells are `Empty`.
+    pub fn infer(cell: Option<&'a str>) -> CellValue<'a> {
+        let text = match cell {
+            Some(text) => text,
+            None => return CellValue::Empty,
+        };
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return CellValue::Empty;
+        }
+
+        if is_number(trimmed) {
+            if let Ok(n) = trimmed.parse::<f64>() {
+                return CellValue::Number(n);
+            }
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+            return CellValue::Date(date);
+        }
+
+        CellValue::Text(text)
+    }
+
+    /// The number of characters the value takes when displayed.
+    fn width(&self) -> usize {
+        match self {
+            CellValue::Empty => 0,
+            CellValue::Number(n) => n.to_string().len(),
+            CellValue::Date(_) => 10,
+            CellValue::Text(t) => t.chars().count(),
+        }
+    }
+}
+
+/// Checks whether `s` looks like a plain decimal number, values with leading
+/// zeros (like zip codes and ids) are kept as text.
+fn is_number(s: &str) -> bool {
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return false;
+    }
+    if digits.matches('.').count() > 1 || digits.starts_with('.') || digits.ends_with('.') {
+        return false;
+    }
+    !(digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0."))
+}
+
+/// Exports the `csv` table to a spreadsheet workbook at `path`, the format is
+/// picked based on the file extension.
+pub fn export(csv: &Csv, path: &str) -> Result<(), Box<dyn Error>> {
+    match SheetFormat::from_path(path)? {
+        SheetFormat::Xlsx => write_xlsx(csv, path),
+        SheetFormat::Ods => write_ods(csv, path),
+    }
+}
+
+/// Loads a single sheet of the workbook at `path` into a `Csv` table, the 
+/// first row of the sheet is the header. The `sheet` can be given by its name
+/// or by its (1-based) index, defaults to the first sheet.
+pub fn import(path: &str, sheet: Option<&str>) -> Result<Csv, Box<dyn Error>> {
+    let mut workbook = open_workbook_auto(path)?;
+    let names = workbook.sheet_names();
+
+    let name = match sheet {
+        None => names.first().cloned().ok_or(format!("The workbook `{path}` has no sheets"))?,
+        Some(s) if names.iter().any(|n| n == s) => s.to_string(),
+        Some(s) => match s.parse::<usize>() {
+            Ok(i) if i >= 1 && i <= names.len() => names[i - 1].clone(),
+            _ => return Err(format!("No sheet `{s}` in `{path}`, available sheets are: {}", names.join(" | ")).into()),
+        },
+    };
+
+    let range = workbook.worksheet_range(&name)?;
+    let mut rows = range.rows().map(|row| row.iter().map(cell_text).collect::<Vec<String>>());
+    let header = rows.next().ok_or(format!("The sheet `{name}` is empty"))?;
+    let rows = rows.map(|row| row.into_iter().map(Some).collect()).collect();
+
+    Ok(Csv::new(header, rows, '='))
+}
+
+/// Renders a workbook cell as text, dates are written in the ISO format so 
+/// they are inferred back as dates when exported.
+fn cell_text(cell: &Data) -> String {
+    let text = match cell {
+        Data::DateTime(d) => match d.as_datetime() {
+            Some(dt) if dt.time() == NaiveTime::MIN => dt.format("%Y-%m-%d").to_string(),
+            Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+            None => d.to_string(),
+        },
+        Data::DateTimeIso(d) => d.strip_suffix("T00:00:00").unwrap_or(d).replace('T', " "),
+        _ => cell.to_string(),
+    };
+
+    // records are line based, so cells can't span multiple lines.
+    text.replace(['\r', '\n'], " ")
+}
+
+/// Calculates the width (in characters) of each column of the table.
+fn column_widths(csv: &Csv) -> Vec<usize> {
+    let mut widths: Vec<usize> = csv.header.iter().map(|h| h.chars().count()).collect();
+    for row in &csv.rows {
+        for (i, cell) in row.iter().enumerate() {
+            let width = CellValue::infer(cell.as_deref()).width();
+            if width > widths[i] {
+                widths[i] = width;
+            }
+        }
+    }
+    widths
+}
+
+fn write_xlsx(csv: &Csv, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    let bold = Format::new().set_bold();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+
+    for (col, title) in csv.header.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, title, &bold)?;
+    }
+
+    for (r, row) in csv.rows.iter().enumerate() {
+        let r = r as u32 + 1;
+        for (col, cell) in row.iter().enumerate() {
+            let col = col as u16;
+            match CellValue::infer(cell.as_deref()) {
+                CellValue::Empty => (),
+                CellValue::Number(n) => { sheet.write_number(r, col, n)?; },
+                CellValue::Date(d) => {
+                    let date = u16::try_from(d.year()).ok()
+                        .and_then(|year| ExcelDateTime::from_ymd(year, d.month() as u8, d.day() as u8).ok());
+                    match date {
+                        Some(date) => { sheet.write_datetime_with_format(r, col, &date, &date_format)?; },
+                        // excel only has dates from 1900 to 9999, the other ones are kept as text.
+                        None => { sheet.write_string(r, col, cell.as_deref().unwrap_or_default())?; },
+                    }
+                },
+                CellValue::Text(t) => { sheet.write_string(r, col, t)?; },
+            }
+        }
+    }
+
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofit();
+    workbook.save(path)?;
+
+    Ok(())
+}
+
+fn write_ods(csv: &Csv, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut workbook = WorkBook::default();
+    let mut bold = CellStyle::new_empty();
+    bold.set_font_bold();
+    let bold = workbook.add_cellstyle(bold);
+    let date_format = workbook.add_datetime_format(format::create_date_iso_format("iso_date"));
+    let date_style = workbook.add_cellstyle(CellStyle::new("iso_date_cell", &date_format));
+
+    let mut sheet = Sheet::new("Sheet1");
+    for (col, title) in csv.header.iter().enumerate() {
+        sheet.set_styled_value(0, col as u32, title.as_str(), &bold);
+    }
+
+    for (r, row) in csv.rows.iter().enumerate() {
+        let r = r as u32 + 1;
+        for (col, cell) in row.iter().enumerate() {
+            let col = col as u32;
+            match CellValue::infer(cell.as_deref()) {
+                CellValue::Empty => (),
+                CellValue::Number(n) => sheet.set_value(r, col, n),
+                CellValue::Date(d) => sheet.set_styled_value(r, col, d, &date_style),
+                CellValue::Text(t) => sheet.set_value(r, col, t),
+            }
+        }
+    }
+
+    // ods has no auto-fit, so we estimate the width out of the characters count.
+    for (col, width) in column_widths(csv).into_iter().enumerate() {
+        sheet.set_col_width(col as u32, Length::Mm(width as f64 * 2.2 + 4.0));
+    }
+    sheet.split_row_header(0);
+
+    workbook.push_sheet(sheet);
+    spreadsheet_ods::write_ods(&mut workbook, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn exports_out_of_range_dates_as_text() {
+        let rows = vec![
+            vec![Some("1800-01-01".to_string())],
+            vec![Some("2024-02-29".to_string())],
+        ];
+        let csv = Csv::new(vec!["date".to_string()], rows, '=');
+        let path = env::temp_dir().join(format!("transformers-dates-{}.xlsx", process::id()));
+        let path = path.to_str().unwrap();
+
+        export(&csv, path).unwrap();
+        let imported = import(path, None);
+        _ = fs::remove_file(path);
+
+        let imported = imported.unwrap();
+        assert_eq!(imported.rows[0][0].as_deref(), Some("1800-01-01"));
+        assert_eq!(imported.rows[1][0].as_deref(), Some("2024-02-29"));
+    }
+}
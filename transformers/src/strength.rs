@@ -0,0 +1,660 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Passwords are analyzed in chunks of this many characters, the patterns
+/// are only searched for within a chunk.
+const MAX_ANALYZED_LENGTH: usize = 100;
+
+/// The guesses needed per character when nothing better is known.
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+
+/// The keyboard rows (unshifted and shifted) used to find spatial patterns,
+/// each row is shifted to the right of the one above it like on a real keyboard.
+const KEYBOARD: [(&str, &str, f64); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+", 0.0),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|", 1.5),
+    ("asdfghjkl;'", "ASDFGHJKL:\"", 1.75),
+    ("zxcvbnm,./", "ZXCVBNM<>?", 2.25),
+];
+
+/// The letters commonly replaced with look-alike characters.
+const LEET: [(char, &[char]); 14] = [
+    ('@', &['a']), ('4', &['a']), ('8', &['b']), ('(', &['c']), ('3', &['e']),
+    ('6', &['g']), ('9', &['g']), ('!', &['i']), ('1', &['i', 'l']), ('|', &['i', 'l']),
+    ('0', &['o']), ('$', &['s']), ('5', &['s']), ('7', &['t']),
+];
+
+/// The kind of guessable pattern a part of the password follows.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Pattern {
+    /// A dictionary or common password word, `leet` tells if look-alike
+    /// characters were substituted in it.
+    Dictionary { word: String, common: bool, leet: bool },
+    /// Keys next to each other on the keyboard, with the number of turns.
+    Spatial { turns: usize },
+    /// The same characters (or group of characters) repeated.
+    Repeat { unit: String },
+    /// Characters following each other in the alphabet or digits.
+    Sequence { ascending: bool },
+    /// A year or a full date.
+    Date,
+    /// Nothing predictable.
+    Bruteforce,
+}
+
+/// A part of the password (from `start` to `end` characters, inclusive)
+/// following a pattern that takes `guesses` tries to be found.
+#[derive(Clone, Debug)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub token: String,
+    pub pattern: Pattern,
+    pub guesses: f64,
+}
+
+/// The result of analyzing a password.
+pub struct Report {
+    pub guesses: f64,
+    pub score: u8,
+    pub sequence: Vec<Match>,
+    pub warning: Option<String>,
+    pub suggestions: Vec<String>,
+}
+
+/// The number of ways to pick `k` out of `n`.
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (1..=k).fold(1.0, |acc, i| acc * (n - k + i) as f64 / i as f64)
+}
+
+/// The number of variations to try when `changed` characters out of
+/// `changed + unchanged` may differ from the plain word (casing or leet).
+fn variations(changed: usize, unchanged: usize) -> f64 {
+    if changed == 0 {
+        return 1.0;
+    }
+    if unchanged == 0 {
+        return 2.0;
+    }
+    (1..=changed.min(unchanged)).map(|i| binomial(changed + unchanged, i)).sum()
+}
+
+/// The guesses needed for the casing of a dictionary `token`, all lower case,
+/// all upper case and capitalized words are the first ones tried.
+fn casing_variations(token: &[char]) -> f64 {
+    let upper = token.iter().filter(|c| c.is_uppercase()).count();
+    let lower = token.iter().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        return 1.0;
+    }
+    let capitalized = token[0].is_uppercase() && upper == 1;
+    let ends_upper = token[token.len() - 1].is_uppercase() && upper == 1;
+    if lower == 0 || capitalized || ends_upper {
+        return 2.0;
+    }
+    variations(upper, lower)
+}
+
+/// Loads a bundled word list into a rank (1-based) lookup table.
+fn ranked(list: &str) -> HashMap<String, usize> {
+    list.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .enumerate()
+        .map(|(i, word)| (word.to_string(), i + 1))
+        .collect()
+}
+
+/// The common passwords, ranked by how common they are.
+fn common_passwords() -> &'static HashMap<String, usize> {
+    static COMMON: OnceLock<HashMap<String, usize>> = OnceLock::new();
+    COMMON.get_or_init(|| ranked(include_str!("words/common-passwords.txt")))
+}
+
+/// The dictionary words (the passphrase word list).
+fn dictionary_words() -> &'static HashMap<String, usize> {
+    static WORDS: OnceLock<HashMap<String, usize>> = OnceLock::new();
+    WORDS.get_or_init(|| ranked(include_str!("words/passphrase.txt")))
+}
+
+/// Expands the leet characters of a (lower cased) token into all the plain
+/// words it may stand for, gives up on tokens with too many ambiguities.
+fn unleet(token: &[char]) -> Vec<(String, usize)> {
+    let mut candidates = vec![(String::new(), 0)];
+    for c in token {
+        let options = LEET.iter().find(|(l, _)| l == c).map(|(_, plain)| *plain);
+        candidates = match options {
+            Some(plain) => candidates.into_iter()
+                .flat_map(|(word, subs)| plain.iter().map(move |p| (format!("{word}{p}"), subs + 1)))
+                .collect(),
+            None => candidates.into_iter().map(|(word, subs)| (format!("{word}{c}"), subs)).collect(),
+        };
+        if candidates.len() > 16 {
+            return vec![];
+        }
+    }
+    candidates.retain(|(_, subs)| *subs > 0);
+    candidates
+}
+
+/// Finds the dictionary words (plain or with leet substitutions) in the password.
+fn dictionary_matches(chars: &[char]) -> Vec<Match> {
+    let (common, words) = (common_passwords(), dictionary_words());
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    if lower.len() != chars.len() {
+        return vec![];
+    }
+
+    let mut matches = vec![];
+    for i in 0..chars.len() {
+        for j in (i + 2)..chars.len() {
+            let token = &chars[i..=j];
+            let plain: String = lower[i..=j].iter().collect();
+            let mut candidates = vec![(plain, 0)];
+            candidates.extend(unleet(&lower[i..=j]));
+
+            for (word, subs) in candidates {
+                let found = common.get(&word).map(|rank| (*rank as f64, true))
+                    .or_else(|| words.get(&word).map(|_| (words.len() as f64, false)));
+                if let Some((rank, is_common)) = found {
+                    let leet = subs > 0;
+                    // the letters that could have been substituted but weren't.
+                    let leetable = word.chars().filter(|c| LEET.iter().any(|(_, plain)| plain.contains(c))).count();
+                    let guesses = rank * casing_variations(token) * variations(subs, leetable - subs);
+                    matches.push(Match {
+                        start: i,
+                        end: j,
+                        token: token.iter().collect(),
+                        pattern: Pattern::Dictionary { word, common: is_common, leet },
+                        guesses,
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// The position of a key on the keyboard and whether it's shifted.
+fn key_position(c: char) -> Option<(f64, f64, bool)> {
+    KEYBOARD.iter().enumerate().find_map(|(row, (plain, shifted, offset))| {
+        plain.chars().position(|k| k == c).map(|col| (row as f64, col as f64 + offset, false))
+            .or_else(|| shifted.chars().position(|k| k == c).map(|col| (row as f64, col as f64 + offset, true)))
+    })
+}
+
+/// Finds runs of (at least 3) keys next to each other on the keyboard.
+fn spatial_matches(chars: &[char]) -> Vec<Match> {
+    let keys: Vec<usize> = KEYBOARD.iter().map(|(plain, _, _)| plain.len()).collect();
+    let starting_positions = keys.iter().sum::<usize>() as f64;
+    let average_degree: f64 = 4.6;
+
+    let mut matches = vec![];
+    let mut i = 0;
+    while i + 2 < chars.len() {
+        let mut j = i;
+        let mut turns = 0;
+        let mut shifted = key_position(chars[i]).is_some_and(|(_, _, s)| s) as usize;
+        let mut direction: Option<(i32, i32)> = None;
+
+        while j + 1 < chars.len() {
+            let (Some(a), Some(b)) = (key_position(chars[j]), key_position(chars[j + 1])) else { break };
+            let (dr, dx) = (b.0 - a.0, b.1 - a.1);
+            if dr.abs() > 1.0 || dx.abs() > 1.0 || (dr == 0.0 && dx == 0.0) {
+                break;
+            }
+            let step = (dr as i32, dx.signum() as i32);
+            if direction != Some(step) {
+                turns += 1;
+                direction = Some(step);
+            }
+            shifted += b.2 as usize;
+            j += 1;
+        }
+
+        if j - i >= 2 {
+            let length = j - i + 1;
+            let mut guesses = 0.0;
+            for l in 2..=length {
+                for t in 1..=turns.min(l - 1) {
+                    guesses += binomial(l - 1, t - 1) * starting_positions * average_degree.powi(t as i32);
+                }
+            }
+            guesses *= variations(shifted, length - shifted);
+            matches.push(Match {
+                start: i,
+                end: j,
+                token: chars[i..=j].iter().collect(),
+                pattern: Pattern::Spatial { turns },
+                guesses,
+            });
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+    matches
+}
+
+/// Finds runs of (at least 3) characters following each other by one.
+fn sequence_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = vec![];
+    let mut i = 0;
+    while i + 2 < chars.len() {
+        let delta = chars[i + 1] as i64 - chars[i] as i64;
+        let mut j = i + 1;
+        if delta.abs() == 1 && chars[i].is_alphanumeric() && chars[j].is_alphanumeric() {
+            while j + 1 < chars.len() && chars[j + 1].is_alphanumeric() && chars[j + 1] as i64 - chars[j] as i64 == delta {
+                j += 1;
+            }
+        }
+
+        if j - i >= 2 {
+            let first = chars[i];
+            let base = if "aAzZ019".contains(first) { 4.0 } else if first.is_ascii_digit() { 10.0 } else { 26.0 };
+            let direction = if delta > 0 { 1.0 } else { 2.0 };
+            matches.push(Match {
+                start: i,
+                end: j,
+                token: chars[i..=j].iter().collect(),
+                pattern: Pattern::Sequence { ascending: delta > 0 },
+                guesses: base * direction * (j - i + 1) as f64,
+            });
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+    matches
+}
+
+/// Finds characters (or groups of characters) repeated back to back.
+fn repeat_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        // pick the unit covering the longest span starting here.
+        let mut best: Option<(usize, usize)> = None;
+        for unit in 1..=(chars.len() - i) / 2 {
+            let mut count = 1;
+            while i + (count + 1) * unit <= chars.len()
+                && chars[i..i + unit] == chars[i + count * unit..i + (count + 1) * unit] {
+                count += 1;
+            }
+            if count > 1 && best.is_none_or(|(u, c)| unit * count > u * c) {
+                best = Some((unit, count));
+            }
+        }
+
+        match best {
+            Some((unit, count)) if unit * count >= 3 => {
+                let unit_chars = &chars[i..i + unit];
+                let unit_guesses = analyze_chars(unit_chars).guesses;
+                let end = i + unit * count - 1;
+                matches.push(Match {
+                    start: i,
+                    end,
+                    token: chars[i..=end].iter().collect(),
+                    pattern: Pattern::Repeat { unit: unit_chars.iter().collect() },
+                    guesses: unit_guesses * count as f64,
+                });
+                i = end + 1;
+            },
+            _ => i += 1,
+        }
+    }
+    matches
+}
+
+/// Checks whether the day, month and year make a (plausible) date.
+fn is_date(day: u32, month: u32, year: i32) -> bool {
+    (1..=31).contains(&day) && (1..=12).contains(&month) && (1900..=2049).contains(&year)
+}
+
+/// Turns a 2 digits year into a full one, `85` is 1985 and `12` is 2012.
+fn full_year(year: i32, digits: usize) -> i32 {
+    match (digits, year) {
+        (2, y) if y > 50 => 1900 + y,
+        (2, y) => 2000 + y,
+        (_, y) => y,
+    }
+}
+
+/// The current year, dates are compared to it when guessing how predictable they are.
+fn reference_year() -> i32 {
+    static YEAR: OnceLock<i32> = OnceLock::new();
+    *YEAR.get_or_init(|| {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        // the average length of a gregorian year is 365.2425 days.
+        1970 + (seconds / 31_556_952) as i32
+    })
+}
+
+/// The number of years around the reference year to try to find `year`.
+fn year_space(year: i32) -> f64 {
+    ((year - reference_year()).abs() as f64).max(20.0)
+}
+
+/// Finds years (1900 to 2049) and full dates written in digits, with or without separators.
+fn date_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = vec![];
+    let text: String = chars.iter().collect();
+    if !text.is_ascii() {
+        return matches;
+    }
+
+    for i in 0..chars.len() {
+        for j in (i + 3)..chars.len().min(i + 10) {
+            let token = &text[i..=j];
+            let mut dates: Vec<(u32, u32, i32)> = vec![];
+            let separated: Vec<&str> = token.split(['/', '-', '.', ' ', '_']).collect();
+            let has_separator = separated.len() == 3;
+
+            if has_separator {
+                let numbers: Vec<Option<u32>> = separated.iter().map(|p| p.parse().ok()).collect();
+                if let [Some(a), Some(b), Some(c)] = numbers[..] {
+                    let (la, lc) = (separated[0].len(), separated[2].len());
+                    dates.push((a, b, full_year(c as i32, lc)));
+                    dates.push((b, a, full_year(c as i32, lc)));
+                    dates.push((c, b, full_year(a as i32, la)));
+                }
+            } else if token.chars().all(|c| c.is_ascii_digit()) {
+                let n = token.len();
+                let part = |from: usize, to: usize| token[from..to].parse::<u32>().unwrap();
+                match n {
+                    4 if (1900..=2049).contains(&(part(0, 4) as i32)) => {
+                        let year = part(0, 4) as i32;
+                        matches.push(Match {
+                            start: i,
+                            end: j,
+                            token: token.to_string(),
+                            pattern: Pattern::Date,
+                            guesses: year_space(year),
+                        });
+                    },
+                    6 | 8 => {
+                        let y = n - 4;
+                        dates.push((part(0, 2), part(2, 4), full_year(part(4, n) as i32, y)));
+                        dates.push((part(2, 4), part(0, 2), full_year(part(4, n) as i32, y)));
+                        dates.push((part(n - 2, n), part(n - 4, n - 2), full_year(part(0, y) as i32, y)));
+                    },
+                    _ => (),
+                }
+            }
+
+            if let Some((_, _, year)) = dates.into_iter().find(|(d, m, y)| is_date(*d, *m, *y)) {
+                let separator = if has_separator { 4.0 } else { 1.0 };
+                matches.push(Match {
+                    start: i,
+                    end: j,
+                    token: token.to_string(),
+                    pattern: Pattern::Date,
+                    guesses: year_space(year) * 365.0 * separator,
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Picks the sequence of matches (filling the gaps with brute force) that
+/// needs the least guesses. Like zxcvbn, the product of the matches guesses
+/// is multiplied by the factorial of their count, since an attacker has to
+/// try the patterns in every order.
+fn most_guessable(chars: &[char], matches: Vec<Match>) -> (f64, Vec<Match>) {
+    let n = chars.len();
+    if n == 0 {
+        return (1.0, vec![]);
+    }
+
+    let mut by_end: Vec<Vec<Match>> = vec![vec![]; n];
+    for m in matches {
+        by_end[m.end].push(m);
+    }
+    for (end, ending) in by_end.iter_mut().enumerate() {
+        for start in 0..=end {
+            ending.push(Match {
+                start,
+                end,
+                token: chars[start..=end].iter().collect(),
+                pattern: Pattern::Bruteforce,
+                guesses: BRUTEFORCE_CARDINALITY.powi((end - start + 1) as i32),
+            });
+        }
+    }
+
+    // best[k][l] is the best product covering the first k characters with l matches.
+    let mut best: Vec<Vec<Option<(f64, Match)>>> = vec![vec![None; n + 1]; n + 1];
+    for (end, ending) in by_end.iter().enumerate() {
+        for m in ending {
+            let k = end + 1;
+            if m.start == 0 {
+                let guesses = m.guesses.max(1.0);
+                if best[k][1].as_ref().is_none_or(|(g, _)| guesses < *g) {
+                    best[k][1] = Some((guesses, m.clone()));
+                }
+                continue;
+            }
+            for l in 1..=m.start {
+                let Some((previous, _)) = &best[m.start][l] else { continue };
+                let guesses = previous * m.guesses.max(1.0);
+                if best[k][l + 1].as_ref().is_none_or(|(g, _)| guesses < *g) {
+                    best[k][l + 1] = Some((guesses, m.clone()));
+                }
+            }
+        }
+    }
+
+    let factorial = |l: usize| (1..=l).fold(1.0, |acc, i| acc * i as f64);
+    let (count, guesses) = (1..=n)
+        .filter_map(|l| best[n][l].as_ref().map(|(g, _)| (l, g * factorial(l))))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+
+    // walk back the chosen matches.
+    let mut sequence = vec![];
+    let (mut k, mut l) = (n, count);
+    while k > 0 {
+        let (_, m) = best[k][l].clone().unwrap();
+        k = m.start;
+        l -= 1;
+        sequence.push(m);
+    }
+    sequence.reverse();
+
+    (guesses, sequence)
+}
+
+/// Finds the most guessable sequence of matches in a chunk of the password.
+fn analyze_chunk(chunk: &[char]) -> (f64, Vec<Match>) {
+    let mut matches = dictionary_matches(chunk);
+    matches.extend(spatial_matches(chunk));
+    matches.extend(sequence_matches(chunk));
+    matches.extend(repeat_matches(chunk));
+    matches.extend(date_matches(chunk));
+    most_guessable(chunk, matches)
+}
+
+/// Analyzes the characters of a password, without the feedback. Long
+/// passwords are split into chunks whose guesses multiply, a chunk repeating
+/// the one before it only doubles the guesses. The guesses of very long
+/// passwords are capped to the largest finite number.
+fn analyze_chars(chars: &[char]) -> Report {
+    let mut guesses: f64 = 1.0;
+    let mut sequence = vec![];
+    let mut previous: Option<&[char]> = None;
+    for (i, chunk) in chars.chunks(MAX_ANALYZED_LENGTH).enumerate() {
+        let offset = i * MAX_ANALYZED_LENGTH;
+        if previous == Some(chunk) {
+            guesses = (guesses * 2.0).min(f64::MAX);
+            sequence.push(Match {
+                start: offset,
+                end: offset + chunk.len() - 1,
+                token: chunk.iter().collect(),
+                pattern: Pattern::Repeat { unit: chunk.iter().collect() },
+                guesses: 2.0,
+            });
+            continue;
+        }
+        let (chunk_guesses, matches) = analyze_chunk(chunk);
+        guesses = (guesses * chunk_guesses).min(f64::MAX);
+        sequence.extend(matches.into_iter().map(|m| Match { start: m.start + offset, end: m.end + offset, ..m }));
+        previous = Some(chunk);
+    }
+    let score = match guesses {
+        g if g < 1e3 => 0,
+        g if g < 1e6 => 1,
+        g if g < 1e8 => 2,
+        g if g < 1e10 => 3,
+        _ => 4,
+    };
+
+    Report { guesses, score, sequence, warning: None, suggestions: vec![] }
+}
+
+/// Builds the warning and suggestions out of the patterns found.
+fn feedback(report: &mut Report, length: usize) {
+    if length == 0 {
+        report.warning = Some("The password is empty.".to_string());
+        report.suggestions.push("Use a few words and avoid common phrases.".to_string());
+        return;
+    }
+    if report.score >= 3 {
+        return;
+    }
+
+    report.suggestions.push("Add another word or two, uncommon words are better.".to_string());
+    if length < 12 {
+        report.suggestions.push("Use a longer password, at least 12 characters (or a passphrase of 4+ words).".to_string());
+    }
+
+    let longest = report.sequence.iter()
+        .filter(|m| m.pattern != Pattern::Bruteforce)
+        .max_by_key(|m| m.end - m.start);
+    let Some(longest) = longest else { return };
+
+    report.warning = Some(match &longest.pattern {
+        Pattern::Dictionary { common: true, .. } => "This is one of the most common passwords.".to_string(),
+        Pattern::Dictionary { .. } => "A single dictionary word is easy to guess.".to_string(),
+        Pattern::Spatial { turns: 1 } => "Straight rows of keys are easy to guess.".to_string(),
+        Pattern::Spatial { .. } => "Short keyboard patterns are easy to guess.".to_string(),
+        Pattern::Repeat { unit } if unit.chars().count() == 1 => "Repeats like \"aaa\" are easy to guess.".to_string(),
+        Pattern::Repeat { .. } => "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\".".to_string(),
+        Pattern::Sequence { .. } => "Sequences like \"abc\" or \"6543\" are easy to guess.".to_string(),
+        Pattern::Date => "Dates and years are easy to guess.".to_string(),
+        Pattern::Bruteforce => unreachable!(),
+    });
+
+    for m in &report.sequence {
+        let suggestion = match &m.pattern {
+            Pattern::Dictionary { leet: true, .. } => "Predictable substitutions like '@' instead of 'a' don't help very much.",
+            Pattern::Dictionary { .. } if casing_variations(&m.token.chars().collect::<Vec<char>>()) > 1.0 => "Capitalization doesn't help very much.",
+            Pattern::Spatial { .. } => "Avoid keyboard patterns, or use longer ones with more turns.",
+            Pattern::Repeat { .. } => "Avoid repeated words and characters.",
+            Pattern::Sequence { .. } => "Avoid sequences.",
+            Pattern::Date => "Avoid dates and years that are associated with you.",
+            _ => continue,
+        };
+        if !report.suggestions.iter().any(|s| s == suggestion) {
+            report.suggestions.push(suggestion.to_string());
+        }
+    }
+}
+
+/// Analyzes the strength of the `password` (zxcvbn-style): finds the
+/// guessable patterns in it, estimates the guesses needed to crack it and
+/// gives suggestions to make it stronger.
+pub fn analyze(password: &str) -> Report {
+    let chars: Vec<char> = password.chars().collect();
+    let mut report = analyze_chars(&chars);
+    feedback(&mut report, chars.len());
+    report
+}
+
+/// Renders a duration in seconds in a human readable way.
+fn display_time(seconds: f64) -> String {
+    let units = [
+        (60.0 * 60.0 * 24.0 * 365.0 * 100.0, "century"),
+        (60.0 * 60.0 * 24.0 * 365.0, "year"),
+        (60.0 * 60.0 * 24.0 * 31.0, "month"),
+        (60.0 * 60.0 * 24.0, "day"),
+        (60.0 * 60.0, "hour"),
+        (60.0, "minute"),
+        (1.0, "second"),
+    ];
+    if seconds < 1.0 {
+        return "less than a second".to_string();
+    }
+    if seconds >= units[0].0 * 100.0 {
+        return "centuries".to_string();
+    }
+    let (size, unit) = units.iter().find(|(size, _)| seconds >= *size).unwrap();
+    let count = (seconds / size).round();
+    match (count as u64, *unit) {
+        (1, unit) => format!("1 {unit}"),
+        (count, "century") => format!("{count} centuries"),
+        (count, unit) => format!("{count} {unit}s"),
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Dictionary { word, common, leet } => {
+                let kind = if *common { "common password" } else { "dictionary word" };
+                let leet = if *leet { " (with leetspeak)" } else { "" };
+                write!(f, "{kind} `{word}`{leet}")
+            },
+            Pattern::Spatial { turns } => write!(f, "keyboard pattern ({turns} turns)"),
+            Pattern::Repeat { unit } => write!(f, "repeat of `{unit}`"),
+            Pattern::Sequence { ascending: true } => write!(f, "ascending sequence"),
+            Pattern::Sequence { ascending: false } => write!(f, "descending sequence"),
+            Pattern::Date => write!(f, "date"),
+            Pattern::Bruteforce => write!(f, "random characters"),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = ["very weak", "weak", "fair", "strong", "very strong"][self.score as usize];
+        writeln!(f, "Score: {}/4 ({verdict})", self.score)?;
+        writeln!(f, "Estimated guesses: 10^{:.1}", self.guesses.log10())?;
+        writeln!(f)?;
+        writeln!(f, "Crack time:")?;
+        let attacks = [
+            ("online attack, throttled (100 guesses/hour)", 100.0 / 3600.0),
+            ("online attack, unthrottled (10 guesses/second)", 10.0),
+            ("offline attack, slow hash (10k guesses/second)", 1e4),
+            ("offline attack, fast hash (10B guesses/second)", 1e10),
+        ];
+        for (attack, rate) in attacks {
+            writeln!(f, "  - {attack}: {}", display_time(self.guesses / rate))?;
+        }
+
+        if self.sequence.iter().any(|m| m.pattern != Pattern::Bruteforce) {
+            writeln!(f)?;
+            writeln!(f, "Patterns found:")?;
+            for m in self.sequence.iter().filter(|m| m.pattern != Pattern::Bruteforce) {
+                writeln!(f, "  - `{}` (characters {} to {}): {}", m.token, m.start + 1, m.end + 1, m.pattern)?;
+            }
+        }
+
+        if let Some(warning) = &self.warning {
+            writeln!(f)?;
+            writeln!(f, "Warning: {warning}")?;
+        }
+        if !self.suggestions.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Suggestions:")?;
+            for suggestion in &self.suggestions {
+                writeln!(f, "  - {suggestion}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::str::FromStr;
+use crate::locale::{self, Locale};
+
+/// The style guides deciding which (small) words stay lower case in a title.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TitleStyle {
+    /// Associated Press: articles, conjunctions and prepositions of up to
+    /// three letters stay lower case.
+    Ap,
+    /// Chicago Manual of Style: articles, coordinating conjunctions and all
+    /// prepositions (whatever their length) stay lower case.
+    Chicago,
+}
+
+impl FromStr for TitleStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ap" => Ok(TitleStyle::Ap),
+            "chicago" => Ok(TitleStyle::Chicago),
+            _ => Err(format!("Unknown title style `{s}`, expected one of: ap, chicago")),
+        }
+    }
+}
+
+const AP_SMALL_WORDS: [&str; 22] = [
+    "a", "an", "the",
+    "and", "but", "for", "nor", "or", "so", "yet",
+    "as", "at", "by", "in", "of", "off", "on", "out", "per", "to", "up", "via",
+];
+
+const CHICAGO_SMALL_WORDS: [&str; 56] = [
+    "a", "an", "the",
+    "and", "but", "for", "nor", "or",
+    "about", "above", "across", "after", "against", "along", "among", "around", "as", "at",
+    "before", "behind", "below", "beneath", "beside", "between", "beyond", "by", "down",
+    "during", "except", "from", "in", "inside", "into", "like", "near", "of", "off", "on",
+    "onto", "out", "over", "past", "per", "since", "through", "throughout", "to", "toward",
+    "under", "until", "up", "upon", "via", "with", "within", "without",
+];
+
+impl TitleStyle {
+    /// Checks whether the (lower cased) `word` stays lower case in the middle of a title.
+    fn is_small_word(&self, word: &str) -> bool {
+        match self {
+            TitleStyle::Ap => AP_SMALL_WORDS.contains(&word),
+            TitleStyle::Chicago => CHICAGO_SMALL_WORDS.contains(&word),
+        }
+    }
+}
+
+/// Checks whether the `word` is an acronym or has a deliberate casing (like
+/// `NASA` or `iPhone`), that is an upper case letter anywhere but first.
+fn is_acronym(word: &str) -> bool {
+    word.chars().skip(1).any(|c| c.is_uppercase())
+}
+
+/// Capitalizes a single hyphen-free word part of a title.
+fn title_part(part: &str, forced: bool, shouting: bool, style: TitleStyle, locale: Locale) -> String {
+    if !shouting && is_acronym(part) {
+        return part.to_string();
+    }
+
+    let lower = locale::to_lower(part, locale);
+    if !forced && style.is_small_word(&lower) {
+        return lower;
+    }
+
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => locale::to_title(first, locale) + chars.as_str(),
+        None => lower,
+    }
+}
+
+/// Title cases a single line, the white spaces between its words are kept as is.
+fn title_line(line: &str, style: TitleStyle, locale: Locale) -> String {
+    // a line in all caps has no acronyms to preserve, it's just shouting.
+    let shouting = !line.chars().any(|c| c.is_lowercase());
+    let word_count = line.split_whitespace().count();
+    let mut output = String::with_capacity(line.len());
+    let mut index = 0;
+    let mut after_break = false;
+
+    for token in line.split_inclusive(char::is_whitespace) {
+        let word = token.trim_end_matches(char::is_whitespace);
+        let spaces = &token[word.len()..];
+        if word.is_empty() {
+            output.push_str(spaces);
+            continue;
+        }
+
+        // punctuation around the word (like quotes and brackets) is kept as is.
+        let core_start = word.find(char::is_alphanumeric).unwrap_or(word.len());
+        let core_end = word.rfind(char::is_alphanumeric).map_or(core_start, |i| i + word[i..].chars().next().unwrap().len_utf8());
+        let core = &word[core_start..core_end.max(core_start)];
+
+        // the first and last words, and a word starting a new sentence or
+        // subtitle are always capitalized.
+        let forced = index == 0 || index == word_count - 1 || after_break;
+        let parts: Vec<String> = core.split('-').enumerate()
+            .map(|(i, part)| title_part(part, forced && i == 0, shouting, style, locale))
+            .collect();
+
+        output.push_str(&word[..core_start]);
+        output.push_str(&parts.join("-"));
+        output.push_str(&word[core_end.max(core_start)..]);
+        output.push_str(spaces);
+
+        after_break = word.ends_with([':', '.', '?', '!']);
+        index += 1;
+    }
+
+    output
+}
+
+/// Title cases each line of the `text` following the `style` guide and the
+/// casing rules of the `locale`.
+pub fn title_case(text: &str, style: TitleStyle, locale: Locale) -> String {
+    text.lines()
+        .map(|line| title_line(line, style, locale))
+        .collect::<Vec<String>>()
+        .join("\n")
+}